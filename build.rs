@@ -0,0 +1,70 @@
+//! Generates the problem registry included by `src/euler/mod.rs`, so that
+//! adding `src/euler/eulerN.rs` is all it takes to register a new problem. The
+//! problem statement is taken from the `/* ... */` comment each module starts
+//! with.
+
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+const PROBLEMS_DIR: &str = "src/euler";
+const MODULE_PREFIX: &str = "euler";
+const OUTPUT_FILE: &str = "problems.rs";
+
+fn main() {
+    println!("cargo:rerun-if-changed={}", PROBLEMS_DIR);
+
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR not set");
+    let problems_dir = Path::new(&manifest_dir).join(PROBLEMS_DIR);
+    let mut numbers = fs::read_dir(&problems_dir)
+        .expect("could not read problems directory")
+        .map(|entry| entry.expect("could not read directory entry").path())
+        .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some("rs"))
+        .filter_map(|path| problem_number(&path))
+        .collect::<Vec<_>>();
+    numbers.sort_unstable();
+
+    let mut code = String::new();
+    for number in numbers.iter() {
+        let path = problems_dir.join(format!("{}{}.rs", MODULE_PREFIX, number));
+        writeln!(code, "#[path = {:?}]", path.display().to_string()).unwrap();
+        writeln!(code, "pub mod {}{};", MODULE_PREFIX, number).unwrap();
+    }
+    writeln!(code).unwrap();
+    writeln!(code, "static PROBLEMS: &[&dyn Problem] = &[").unwrap();
+    for number in numbers.iter() {
+        writeln!(code, "    &{}{}::Euler{},", MODULE_PREFIX, number, number).unwrap();
+    }
+    writeln!(code, "];").unwrap();
+    writeln!(code).unwrap();
+    writeln!(code, "static STATEMENTS: &[(usize, &str)] = &[").unwrap();
+    for number in numbers.iter() {
+        let path = problems_dir.join(format!("{}{}.rs", MODULE_PREFIX, number));
+        let source = fs::read_to_string(&path).expect("could not read problem");
+        if let Some(statement) = header_comment(&source) {
+            writeln!(code, "    ({}, {:?}),", number, statement).unwrap();
+        }
+    }
+    writeln!(code, "];").unwrap();
+    writeln!(code).unwrap();
+    writeln!(code, "#[cfg(test)]").unwrap();
+    writeln!(code, "const MODULE_NUMBERS: &[usize] = &{:?};", numbers).unwrap();
+
+    let out_dir = env::var("OUT_DIR").expect("OUT_DIR not set");
+    fs::write(Path::new(&out_dir).join(OUTPUT_FILE), code).expect("could not write registry");
+}
+
+fn problem_number(path: &Path) -> Option<usize> {
+    path.file_stem()?
+        .to_str()?
+        .strip_prefix(MODULE_PREFIX)?
+        .parse()
+        .ok()
+}
+
+fn header_comment(source: &str) -> Option<&str> {
+    let comment = source.trim_start().strip_prefix("/*")?;
+    let end = comment.find("*/")?;
+    Some(comment[..end].trim())
+}
@@ -0,0 +1,51 @@
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+
+/// Known answers, one `<problem> <answer>` pair per line.
+const FILE_CONTENTS: &str = include_str!("../static/answers.txt");
+
+lazy_static! {
+    static ref ANSWERS: HashMap<usize, &'static str> = parse_answers(FILE_CONTENTS);
+}
+
+pub fn known_answer(problem: usize) -> Option<&'static str> {
+    ANSWERS.get(&problem).copied()
+}
+
+fn parse_answers(contents: &str) -> HashMap<usize, &str> {
+    contents
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let (problem, answer) = line.split_once(' ').expect("missing answer");
+            let problem = problem.parse().expect("invalid problem number");
+            (problem, answer.trim())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::euler;
+
+    #[test]
+    fn parse() {
+        let answers = parse_answers("1 233168\n13 5537376230\n\n27 -59231\n");
+        assert_eq!(3, answers.len());
+        assert_eq!(Some(&"233168"), answers.get(&1));
+        assert_eq!(Some(&"5537376230"), answers.get(&13));
+        assert_eq!(Some(&"-59231"), answers.get(&27));
+    }
+
+    #[test]
+    fn every_problem_has_known_answer() {
+        for problem in euler::all() {
+            assert!(
+                known_answer(problem.number()).is_some(),
+                "No known answer for problem {}",
+                problem.number()
+            );
+        }
+    }
+}
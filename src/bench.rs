@@ -0,0 +1,108 @@
+use crate::problem::Problem;
+use std::hint;
+use std::time::{Duration, Instant};
+
+/// Summary statistics over the timings of repeated runs of a solver.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub iterations: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    /// Returns `None` if there are no samples.
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+        let n = sorted.len();
+        // The two middle samples are the same one when n is odd
+        let median = (sorted[(n - 1) / 2] + sorted[n / 2]) / 2;
+        let mean = sorted.iter().sum::<Duration>() / n as u32;
+        // Sample standard deviation, since the runs are a sample of all the
+        // runs we could have made.
+        let variance = if n > 1 {
+            let mean = mean.as_secs_f64();
+            sorted
+                .iter()
+                .map(|sample| (sample.as_secs_f64() - mean).powi(2))
+                .sum::<f64>()
+                / (n - 1) as f64
+        } else {
+            0.0
+        };
+        Some(Stats {
+            iterations: n,
+            min: sorted[0],
+            median,
+            mean,
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        })
+    }
+}
+
+/// Solves `problem` `warmup` times without timing it, then `iterations` more
+/// times, timing each run.
+pub fn bench(problem: &dyn Problem, warmup: usize, iterations: usize) -> Option<Stats> {
+    for _ in 0..warmup {
+        hint::black_box(problem.solve());
+    }
+    let samples = (0..iterations)
+        .map(|_| {
+            let start = Instant::now();
+            hint::black_box(problem.solve());
+            start.elapsed()
+        })
+        .collect::<Vec<_>>();
+    Stats::from_samples(&samples)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn millis(ms: &[u64]) -> Vec<Duration> {
+        ms.iter().copied().map(Duration::from_millis).collect()
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(None, Stats::from_samples(&[]));
+    }
+
+    #[test]
+    fn single() {
+        let stats = Stats::from_samples(&millis(&[5])).unwrap();
+        assert_eq!(1, stats.iterations);
+        assert_eq!(Duration::from_millis(5), stats.min);
+        assert_eq!(Duration::from_millis(5), stats.median);
+        assert_eq!(Duration::from_millis(5), stats.mean);
+        assert_eq!(Duration::ZERO, stats.stddev);
+    }
+
+    #[test]
+    fn odd() {
+        let stats = Stats::from_samples(&millis(&[9, 2, 5])).unwrap();
+        assert_eq!(3, stats.iterations);
+        assert_eq!(Duration::from_millis(2), stats.min);
+        assert_eq!(Duration::from_millis(5), stats.median);
+        assert_eq!(Duration::from_millis(16) / 3, stats.mean);
+    }
+
+    #[test]
+    fn even() {
+        let stats = Stats::from_samples(&millis(&[2, 4, 4, 4, 5, 5, 7, 9])).unwrap();
+        assert_eq!(8, stats.iterations);
+        assert_eq!(Duration::from_millis(2), stats.min);
+        assert_eq!(Duration::from_micros(4_500), stats.median);
+        assert_eq!(Duration::from_millis(5), stats.mean);
+        // The sample variance is 32 / 7 ms^2
+        let expected = (32.0f64 / 7.0).sqrt() / 1000.0;
+        assert!((stats.stddev.as_secs_f64() - expected).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,24 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Like `eprintln!`, but only prints when diagnostics are enabled (`--verbose`
+/// on the command line). Solvers use this instead of printing directly so that
+/// stdout only ever contains answers.
+macro_rules! diagnostic {
+    ($($arg:tt)*) => {
+        if $crate::diagnostics::is_enabled() {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+pub(crate) use diagnostic;
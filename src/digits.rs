@@ -2,26 +2,47 @@ use num_traits::*;
 use std::fmt::Debug;
 
 pub trait DigitNum:
-    Num + NumRef + NumOps + NumAssignOps + FromPrimitive + ToPrimitive + Ord + Debug + Clone
+    Num
+    + NumRef
+    + NumOps
+    + NumAssignOps
+    + CheckedMul
+    + FromPrimitive
+    + ToPrimitive
+    + Ord
+    + Debug
+    + Clone
 {
 }
 
 impl<T> DigitNum for T where
-    T: Num + NumRef + NumOps + NumAssignOps + FromPrimitive + ToPrimitive + Ord + Debug + Clone
+    T: Num
+        + NumRef
+        + NumOps
+        + NumAssignOps
+        + CheckedMul
+        + FromPrimitive
+        + ToPrimitive
+        + Ord
+        + Debug
+        + Clone
 {
 }
 
+/// The digits of a number, most significant first, or least significant first
+/// with `rev`. Zero has the single digit 0.
 pub struct Digits<T> {
+    /// The value of the digits not yet taken.
     n: T,
+    /// The place value of the most significant digit not yet taken.
     divisor: T,
     base: T,
+    len: usize,
 }
 
-pub struct DigitsRev<T> {
-    n: T,
-    base: T,
-    started: bool,
-}
+/// The number of digits past which `Digits` finds the most significant one
+/// with [`largest_power`].
+const SHORT_LEN: usize = 16;
 
 pub const DECIMAL_BASE: u8 = 10;
 pub const BINARY_BASE: u8 = 2;
@@ -32,70 +53,75 @@ impl<T> Digits<T>
 where
     T: DigitNum,
 {
-    #[allow(dead_code)]
     pub fn decimal(n: T) -> Self {
         Self::new(n, DECIMAL_BASE)
     }
 
-    #[allow(dead_code)]
     pub fn binary(n: T) -> Self {
         Self::new(n, BINARY_BASE)
     }
 
-    #[allow(dead_code)]
     pub fn octal(n: T) -> Self {
         Self::new(n, OCTAL_BASE)
     }
 
-    #[allow(dead_code)]
     pub fn hex(n: T) -> Self {
         Self::new(n, HEX_BASE)
     }
 
-    fn new(n: T, base: u8) -> Self {
-        let base = T::from_u8(base).unwrap();
-        let mut divisor = T::one();
-        while n >= divisor.clone() * &base {
-            divisor *= base.clone();
+    /// The digits of `n` in any base from 2 to 255.
+    pub fn with_base(n: T, base: u8) -> Result<Self, String> {
+        if base < BINARY_BASE {
+            return Err(format!("Base must be at least 2, not {}", base));
         }
-        Digits { n, divisor, base }
-    }
-}
-
-impl<T> DigitsRev<T>
-where
-    T: DigitNum,
-{
-    #[allow(dead_code)]
-    pub fn decimal(n: T) -> Self {
-        Self::new(n, 10)
-    }
-
-    #[allow(dead_code)]
-    pub fn binary(n: T) -> Self {
-        Self::new(n, 2)
-    }
-
-    #[allow(dead_code)]
-    pub fn octal(n: T) -> Self {
-        Self::new(n, 8)
-    }
-
-    #[allow(dead_code)]
-    pub fn hex(n: T) -> Self {
-        Self::new(n, 16)
+        Ok(Self::new(n, base))
     }
 
     fn new(n: T, base: u8) -> Self {
         let base = T::from_u8(base).unwrap();
-        Self {
+        // Comparing against n / base rather than multiplying up past n keeps
+        // the divisor from overflowing
+        let limit = n.clone() / base.clone();
+        let mut divisor = T::one();
+        let mut len = 1;
+        while divisor <= limit {
+            // A digit at a time is quickest for short numbers, but long ones
+            // need far fewer multiplications jumping ahead by larger powers
+            if len == SHORT_LEN {
+                let (power, exponent) =
+                    largest_power(&(n.clone() / divisor.clone()), base.clone(), 1);
+                divisor *= power;
+                len += exponent;
+                break;
+            }
+            divisor *= base.clone();
+            len += 1;
+        }
+        Digits {
             n,
+            divisor,
             base,
-            started: false,
+            len,
         }
     }
 }
 
+/// The largest power of the base not above `n`, or 1 if there is none, and its
+/// exponent, given `power` = base^`exponent` with `exponent` a power of two.
+///
+/// Squaring the base up to n and then building the exponent bit by bit takes
+/// O(log len) multiplications rather than one per digit.
+fn largest_power<T: DigitNum>(n: &T, power: T, exponent: usize) -> (T, usize) {
+    let (divisor, divisor_exponent) = match power.checked_mul(&power).filter(|square| square <= n) {
+        Some(square) => largest_power(n, square, 2 * exponent),
+        None => (T::one(), 0),
+    };
+    match divisor.checked_mul(&power).filter(|product| product <= n) {
+        Some(product) => (product, divisor_exponent + exponent),
+        None => (divisor, divisor_exponent),
+    }
+}
+
 impl<T> Iterator for Digits<T>
 where
     T: DigitNum,
@@ -103,41 +129,43 @@ where
     type Item = u8;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.divisor == zero() {
+        if self.len == 0 {
             return None;
         }
         let v = (self.n.clone() / self.divisor.clone()).to_u8().unwrap();
         self.n %= self.divisor.clone();
         self.divisor /= self.base.clone();
+        self.len -= 1;
         Some(v)
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
 }
 
-impl<T> Iterator for DigitsRev<T>
+impl<T> DoubleEndedIterator for Digits<T>
 where
     T: DigitNum,
 {
-    type Item = u8;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.n == zero() {
-            return if self.started {
-                None
-            } else {
-                self.started = true;
-                Some(0)
-            };
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
         }
-        self.started = true;
         let v = (self.n.clone() % self.base.clone()).to_u8().unwrap();
         self.n /= self.base.clone();
+        self.divisor /= self.base.clone();
+        self.len -= 1;
         Some(v)
     }
 }
 
+impl<T> ExactSizeIterator for Digits<T> where T: DigitNum {}
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use num_bigint::BigUint;
 
     #[test]
     fn digits_normal() {
@@ -168,7 +196,7 @@ mod test {
     #[test]
     fn digits_rev_normal() {
         let num = 785;
-        let mut digits = DigitsRev::decimal(num);
+        let mut digits = Digits::decimal(num).rev();
         assert_eq!(digits.next(), Some(5));
         assert_eq!(digits.next(), Some(8));
         assert_eq!(digits.next(), Some(7));
@@ -178,7 +206,7 @@ mod test {
     #[test]
     fn digits_rev_single_digit() {
         let num = 7;
-        let mut digits = DigitsRev::decimal(num);
+        let mut digits = Digits::decimal(num).rev();
         assert_eq!(digits.next(), Some(7));
         assert_eq!(digits.next(), None);
     }
@@ -186,7 +214,7 @@ mod test {
     #[test]
     fn digits_rev_zero() {
         let num = 0;
-        let mut digits = DigitsRev::decimal(num);
+        let mut digits = Digits::decimal(num).rev();
         assert_eq!(digits.next(), Some(0));
         assert_eq!(digits.next(), None);
     }
@@ -221,7 +249,7 @@ mod test {
     #[test]
     fn binary_rev_normal() {
         let num = 11;
-        let mut digits = DigitsRev::binary(num);
+        let mut digits = Digits::binary(num).rev();
         assert_eq!(digits.next(), Some(1));
         assert_eq!(digits.next(), Some(1));
         assert_eq!(digits.next(), Some(0));
@@ -232,7 +260,7 @@ mod test {
     #[test]
     fn binary_rev_single_digit() {
         let num = 1;
-        let mut digits = DigitsRev::binary(num);
+        let mut digits = Digits::binary(num).rev();
         assert_eq!(digits.next(), Some(1));
         assert_eq!(digits.next(), None);
     }
@@ -240,7 +268,7 @@ mod test {
     #[test]
     fn binary_rev_zero() {
         let num = 0;
-        let mut digits = DigitsRev::binary(num);
+        let mut digits = Digits::binary(num).rev();
         assert_eq!(digits.next(), Some(0));
         assert_eq!(digits.next(), None);
     }
@@ -273,7 +301,7 @@ mod test {
     #[test]
     fn hex_rev_normal() {
         let num = (16 * 2) + 11;
-        let mut digits = DigitsRev::hex(num);
+        let mut digits = Digits::hex(num).rev();
         assert_eq!(digits.next(), Some(11));
         assert_eq!(digits.next(), Some(2));
         assert_eq!(digits.next(), None);
@@ -282,7 +310,7 @@ mod test {
     #[test]
     fn hex_rev_single_digit() {
         let num = 12;
-        let mut digits = DigitsRev::hex(num);
+        let mut digits = Digits::hex(num).rev();
         assert_eq!(digits.next(), Some(12));
         assert_eq!(digits.next(), None);
     }
@@ -290,8 +318,99 @@ mod test {
     #[test]
     fn hex_rev_zero() {
         let num = 0;
-        let mut digits = DigitsRev::hex(num);
+        let mut digits = Digits::hex(num).rev();
         assert_eq!(digits.next(), Some(0));
         assert_eq!(digits.next(), None);
     }
+
+    #[test]
+    fn other_bases() {
+        let digits = Digits::with_base(100, 3).unwrap().collect::<Vec<_>>();
+        assert_eq!(vec![1, 0, 2, 0, 1], digits);
+        let digits = Digits::with_base(100, 3).unwrap().rev().collect::<Vec<_>>();
+        assert_eq!(vec![1, 0, 2, 0, 1], digits);
+        let digits = Digits::with_base(1_000u32, 255)
+            .unwrap()
+            .collect::<Vec<_>>();
+        assert_eq!(vec![3, 235], digits);
+        let digits = Digits::with_base(254u8, 255)
+            .unwrap()
+            .rev()
+            .collect::<Vec<_>>();
+        assert_eq!(vec![254], digits);
+        let digits = Digits::with_base(0, 7).unwrap().collect::<Vec<_>>();
+        assert_eq!(vec![0], digits);
+    }
+
+    #[test]
+    fn invalid_bases() {
+        assert!(Digits::with_base(10, 0).is_err());
+        assert!(Digits::with_base(10, 1).is_err());
+        assert!(Digits::with_base(10, 2).is_ok());
+    }
+
+    #[test]
+    fn len() {
+        assert_eq!(1, Digits::decimal(0).len());
+        assert_eq!(1, Digits::decimal(9).len());
+        assert_eq!(2, Digits::decimal(10).len());
+        assert_eq!(13, Digits::decimal(1_234_567_890_123u64).len());
+        assert_eq!(20, Digits::decimal(u64::MAX).len());
+        assert_eq!(3, Digits::decimal(255u8).len());
+        assert_eq!(64, Digits::binary(u64::MAX).len());
+        assert_eq!(2, Digits::with_base(255u8, 255).unwrap().len());
+        let mut digits = Digits::decimal(12_345);
+        digits.next();
+        digits.next_back();
+        assert_eq!(3, digits.len());
+        assert_eq!(vec![2, 3, 4], digits.collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn long() {
+        assert_eq!(39, Digits::decimal(u128::MAX).len());
+        assert_eq!(128, Digits::binary(u128::MAX).len());
+        for k in 1..300u32 {
+            let power = BigUint::from(10u32).pow(k);
+            assert_eq!(k as usize + 1, Digits::decimal(power.clone()).len());
+            assert_eq!(k as usize, Digits::decimal(power.clone() - 1u32).len());
+            let digits = Digits::decimal(power + 7u32).collect::<Vec<_>>();
+            assert_eq!(Some(&1), digits.first());
+            assert_eq!(Some(&7), digits.last());
+        }
+    }
+
+    #[test]
+    fn both_ends() {
+        let mut digits = Digits::decimal(1_020_304);
+        assert_eq!(Some(1), digits.next());
+        assert_eq!(Some(4), digits.next_back());
+        assert_eq!(Some(0), digits.next());
+        assert_eq!(Some(0), digits.next_back());
+        assert_eq!(Some(2), digits.next());
+        assert_eq!(Some(3), digits.next_back());
+        assert_eq!(Some(0), digits.next());
+        assert_eq!(None, digits.next_back());
+        assert_eq!(None, digits.next());
+        // Palindromes read the same from both ends
+        let is_palindrome = |n: u32, base| {
+            let digits = Digits::with_base(n, base).unwrap();
+            let len = digits.len();
+            digits
+                .zip(Digits::with_base(n, base).unwrap().rev())
+                .take(len / 2)
+                .all(|(a, b)| a == b)
+        };
+        assert!(is_palindrome(585, 10));
+        assert!(is_palindrome(585, 2));
+        assert!(!is_palindrome(586, 10));
+    }
+
+    #[test]
+    fn near_max() {
+        let digits = Digits::decimal(u8::MAX).collect::<Vec<_>>();
+        assert_eq!(vec![2, 5, 5], digits);
+        let digits = Digits::decimal(u64::MAX).rev().take(3).collect::<Vec<_>>();
+        assert_eq!(vec![5, 1, 6], digits);
+    }
 }
@@ -5,6 +5,9 @@ If we list all the natural numbers below 10 that are multiples of 3 or 5, we get
 Find the sum of all the multiples of 3 or 5 below 1000.
 */
 
+use crate::params::{List, Param, Params};
+use crate::problem::{Answer, Problem};
+
 fn divisble_by(num: u32, divisor: u32) -> bool {
     num % divisor == 0
 }
@@ -24,8 +27,38 @@ fn solve(divisors: &[u32], limit: u32) -> u64 {
 const LIMIT: u32 = 1_000;
 const DIVISORS: [u32; 2] = [3, 5];
 
-pub fn main() {
-    println!("{}", solve(&DIVISORS, LIMIT));
+pub struct Euler1;
+
+impl Problem for Euler1 {
+    fn number(&self) -> usize {
+        1
+    }
+
+    fn title(&self) -> &'static str {
+        "Multiples of 3 or 5"
+    }
+
+    fn params(&self) -> Vec<Param> {
+        vec![
+            Param::new("divisors", "Sum multiples of any of these numbers", List(DIVISORS.to_vec()))
+                .check(|divisors: &List<u32>| {
+                    if divisors.0.contains(&0) {
+                        Err(String::from("must all be positive"))
+                    } else {
+                        Ok(())
+                    }
+                }),
+            Param::new("limit", "Sum multiples below this number", LIMIT),
+        ]
+    }
+
+    fn solve(&self) -> Answer {
+        solve(&DIVISORS, LIMIT).into()
+    }
+
+    fn solve_with(&self, params: &Params) -> Answer {
+        solve(&params.get::<List<u32>>("divisors").0, params.get("limit")).into()
+    }
 }
 
 #[cfg(test)]
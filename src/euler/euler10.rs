@@ -4,33 +4,44 @@ The sum of the primes below 10 is 2 + 3 + 5 + 7 = 17.
 Find the sum of all the primes below two million.
 */
 
-fn primes_below(limit: usize) -> Vec<usize> {
-    let mut is_composite = vec![false; limit];
-    let mut primes = Vec::new();
-    for num in 2..limit {
-        if is_composite[num] {
-            continue;
-        }
-        for index in (num..).step_by(num).take_while(|&x| x < limit) {
-            is_composite[index] = true;
-        }
-        primes.push(num);
-    }
-    primes
-}
+use crate::number_theory::prime_counting::prime_sum;
+use crate::params::{Param, Params};
+use crate::problem::{Answer, Problem};
 
-fn solve(limit: usize) -> usize {
-    primes_below(limit).iter().sum()
+fn solve(limit: u64) -> u128 {
+    prime_sum(limit.saturating_sub(1))
 }
 
-const LIMIT: usize = 2_000_000;
+const LIMIT: u64 = 2_000_000;
+
+pub struct Euler10;
+
+impl Problem for Euler10 {
+    fn number(&self) -> usize {
+        10
+    }
+
+    fn title(&self) -> &'static str {
+        "Summation of primes"
+    }
+
+    fn params(&self) -> Vec<Param> {
+        vec![
+            Param::new("limit", "Sum primes below this number", LIMIT),
+        ]
+    }
 
-pub fn main() {
-    println!("{}", solve(LIMIT));
+    fn solve(&self) -> Answer {
+        solve(LIMIT).into()
+    }
+
+    fn solve_with(&self, params: &Params) -> Answer {
+        solve(params.get("limit")).into()
+    }
 }
 
 #[cfg(test)]
-const ANSWER: usize = 142913828922;
+const ANSWER: u128 = 142913828922;
 
 #[test]
 fn test() {
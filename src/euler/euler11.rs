@@ -28,6 +28,8 @@ What is the greatest product of four adjacent numbers in the same direction (up,
 down, left, right, or diagonally) in the 20×20 grid?
  */
 
+use crate::problem::{Answer, Problem};
+
 type Grid = [[u64; GRID_WIDTH]; GRID_HEIGHT];
 
 fn max_horizontal_sequence(grid: &Grid, sequence_length: usize) -> u64 {
@@ -165,8 +167,20 @@ const GRID: Grid = [
 ];
 const SEQUENCE_LENGTH: usize = 4;
 
-pub fn main() {
-    println!("{}", solve(&GRID, SEQUENCE_LENGTH));
+pub struct Euler11;
+
+impl Problem for Euler11 {
+    fn number(&self) -> usize {
+        11
+    }
+
+    fn title(&self) -> &'static str {
+        "Largest product in a grid"
+    }
+
+    fn solve(&self) -> Answer {
+        solve(&GRID, SEQUENCE_LENGTH).into()
+    }
 }
 
 #[cfg(test)]
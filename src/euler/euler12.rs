@@ -21,55 +21,9 @@ What is the value of the first triangle number to have over five hundred
 divisors?
 */
 
-use multiset::HashMultiSet;
-
-fn primes_below(limit: usize) -> Vec<usize> {
-    let mut is_composite = vec![false; limit];
-    let mut primes = Vec::new();
-    for num in 2..limit {
-        if is_composite[num] {
-            continue;
-        }
-        for index in (num..).step_by(num).take_while(|&x| x < limit) {
-            is_composite[index] = true;
-        }
-        primes.push(num);
-    }
-    primes
-}
-
-fn get_factors(number: usize, primes: &[usize]) -> HashMultiSet<usize> {
-    if number < 2 {
-        return HashMultiSet::new();
-    }
-    let mut factors = HashMultiSet::new();
-    let mut number = number;
-    for &prime in primes {
-        while divisible_by(number, prime) {
-            factors.insert(prime);
-            number /= prime;
-        }
-        if number == 1 {
-            break;
-        }
-    }
-    if number > 1 {
-        factors.insert(number);
-    }
-    factors
-}
-
-fn divisible_by(number: usize, divisor: usize) -> bool {
-    number % divisor == 0
-}
-
-fn num_divisors(number: usize, primes: &[usize]) -> usize {
-    let factors = get_factors(number, primes);
-    factors
-        .distinct_elements()
-        .map(|factor| factors.count_of(factor) + 1)
-        .product()
-}
+use crate::number_theory::factorization::factorize;
+use crate::params::{Param, Params};
+use crate::problem::{Answer, Problem};
 
 struct TriangleNumbers {
     number: usize,
@@ -93,16 +47,40 @@ impl Iterator for TriangleNumbers {
 }
 
 fn solve(min_num_divisors: usize) -> usize {
-    let primes = primes_below(min_num_divisors * 100);
     TriangleNumbers::new()
-        .find(|&number| num_divisors(number, &primes) > min_num_divisors)
+        .find(|&number| factorize(number).tau() > min_num_divisors)
         .expect("no solution found")
 }
 
 const MIN_NUM_DIVISORS: usize = 500;
+// Finding a triangle number with more divisors than this takes about a minute
+const MAX_MIN_NUM_DIVISORS: usize = 10_000;
 
-pub fn main() {
-    println!("{}", solve(MIN_NUM_DIVISORS));
+pub struct Euler12;
+
+impl Problem for Euler12 {
+    fn number(&self) -> usize {
+        12
+    }
+
+    fn title(&self) -> &'static str {
+        "Highly divisible triangular number"
+    }
+
+    fn params(&self) -> Vec<Param> {
+        vec![
+            Param::new("min_divisors", "Find a triangle number with more divisors than this", MIN_NUM_DIVISORS)
+                .at_most(MAX_MIN_NUM_DIVISORS),
+        ]
+    }
+
+    fn solve(&self) -> Answer {
+        solve(MIN_NUM_DIVISORS).into()
+    }
+
+    fn solve_with(&self, params: &Params) -> Answer {
+        solve(params.get("min_divisors")).into()
+    }
 }
 
 #[cfg(test)]
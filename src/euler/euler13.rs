@@ -1,3 +1,16 @@
+/*
+Work out the first ten digits of the sum of the following one-hundred 50-digit
+numbers.
+
+37107287533902102798797998220837590246510135740250
+46376937677490009712648124896970078050417018260538
+...
+20849603980134001723930671666823555245252804609722
+53503534226472524250874054075591789781264330331690
+*/
+
+use crate::problem::{Answer, Problem};
+
 fn sum_digits(digits_grid: &[Vec<u32>]) -> Vec<u32> {
     let max_num_digits = match digits_grid.iter().map(|digits| digits.len()).max() {
         Some(num) => num,
@@ -148,8 +161,20 @@ fn solve(string_grid: &[&str], num_digits: usize) -> String {
         .collect()
 }
 
-pub fn main() {
-    println!("{}", solve(&NUMBER_STRING_GRID, NUM_DIGITS));
+pub struct Euler13;
+
+impl Problem for Euler13 {
+    fn number(&self) -> usize {
+        13
+    }
+
+    fn title(&self) -> &'static str {
+        "Large sum"
+    }
+
+    fn solve(&self) -> Answer {
+        solve(&NUMBER_STRING_GRID, NUM_DIGITS).into()
+    }
 }
 
 #[cfg(test)]
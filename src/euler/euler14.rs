@@ -17,6 +17,9 @@ Which starting number, under one million, produces the longest chain?
 NOTE: Once the chain starts the terms are allowed to go above one million.
 */
 
+use crate::params::{Param, Params};
+use crate::problem::{Answer, Problem};
+
 fn next_collatz(number: usize) -> usize {
     if number % 2 == 0 {
         number / 2
@@ -59,9 +62,35 @@ fn solve(limit: usize) -> usize {
 }
 
 const LIMIT: usize = 1_000_000;
+// Each number below the limit takes 16 bytes, so this needs 1.6 GB
+const MAX_LIMIT: usize = 100_000_000;
+
+pub struct Euler14;
+
+impl Problem for Euler14 {
+    fn number(&self) -> usize {
+        14
+    }
+
+    fn title(&self) -> &'static str {
+        "Longest Collatz sequence"
+    }
 
-pub fn main() {
-    println!("{}", solve(LIMIT));
+    fn params(&self) -> Vec<Param> {
+        vec![
+            Param::new("limit", "Consider starting numbers below this one", LIMIT)
+                .at_least(2usize)
+                .at_most(MAX_LIMIT),
+        ]
+    }
+
+    fn solve(&self) -> Answer {
+        solve(LIMIT).into()
+    }
+
+    fn solve_with(&self, params: &Params) -> Answer {
+        solve(params.get("limit")).into()
+    }
 }
 
 #[cfg(test)]
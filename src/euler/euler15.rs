@@ -11,26 +11,50 @@ the right and down, there are exactly 6 routes to the bottom right corner.
 How many such routes are there through a 20×20 grid?
 */
 
-use num_rational::Rational64;
-
-fn solve(width: i64) -> i64 {
-    (1..=width)
-        .fold(Rational64::new(1i64, 1), |acc, i| {
-            acc * Rational64::new(width + i, i)
-        })
-        .to_integer()
+use crate::params::{Param, Params};
+use crate::problem::{Answer, Problem};
+
+use num_bigint::BigUint;
+use num_traits::One;
+
+// The binomial coefficient (2 * width choose width). Each partial product is
+// itself a binomial coefficient, so the divisions are exact.
+fn solve(width: u64) -> BigUint {
+    (1..=width).fold(BigUint::one(), |acc, i| acc * (width + i) / i)
 }
 
-const WIDTH: i64 = 20;
+const WIDTH: u64 = 20;
+
+pub struct Euler15;
+
+impl Problem for Euler15 {
+    fn number(&self) -> usize {
+        15
+    }
+
+    fn title(&self) -> &'static str {
+        "Lattice paths"
+    }
+
+    fn params(&self) -> Vec<Param> {
+        vec![
+            Param::new("width", "Width and height of the grid", WIDTH),
+        ]
+    }
+
+    fn solve(&self) -> Answer {
+        solve(WIDTH).into()
+    }
 
-pub fn main() {
-    println!("{}", solve(WIDTH));
+    fn solve_with(&self, params: &Params) -> Answer {
+        solve(params.get("width")).into()
+    }
 }
 
 #[cfg(test)]
-const ANSWER: i64 = 137846528820;
+const ANSWER: u64 = 137846528820;
 
 #[test]
 fn test() {
-    assert_eq!(ANSWER, solve(WIDTH));
+    assert_eq!(BigUint::from(ANSWER), solve(WIDTH));
 }
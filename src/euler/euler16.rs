@@ -4,6 +4,9 @@
 What is the sum of the digits of the number 2^1000?
  */
 
+use crate::params::{Param, Params};
+use crate::problem::{Answer, Problem};
+
 use num_bigint::BigUint;
 use num_traits::pow::Pow;
 
@@ -18,8 +21,31 @@ const EXPONENT: u32 = 1_000;
 
 const NUMBER_SYSTEM_BASE: u32 = 10;
 
-pub fn main() {
-    println!("{}", solve(BASE, EXPONENT));
+pub struct Euler16;
+
+impl Problem for Euler16 {
+    fn number(&self) -> usize {
+        16
+    }
+
+    fn title(&self) -> &'static str {
+        "Power digit sum"
+    }
+
+    fn params(&self) -> Vec<Param> {
+        vec![
+            Param::new("base", "Base of the power", BASE),
+            Param::new("exponent", "Exponent of the power", EXPONENT),
+        ]
+    }
+
+    fn solve(&self) -> Answer {
+        solve(BASE, EXPONENT).into()
+    }
+
+    fn solve_with(&self, params: &Params) -> Answer {
+        solve(params.get("base"), params.get("exponent")).into()
+    }
 }
 
 #[cfg(test)]
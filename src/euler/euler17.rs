@@ -12,6 +12,8 @@ letters. The use of "and" when writing out numbers is in compliance with British
 usage.
 */
 
+use crate::params::{Param, Params};
+use crate::problem::{Answer, Problem};
 use crate::say;
 
 fn count_letters(word: &str) -> usize {
@@ -27,8 +29,31 @@ fn solve(begin: u64, end_inclusive: u64) -> usize {
 const BEGIN: u64 = 1;
 const END_INCLUSIVE: u64 = 1_000;
 
-pub fn main() {
-    println!("{}", solve(BEGIN, END_INCLUSIVE));
+pub struct Euler17;
+
+impl Problem for Euler17 {
+    fn number(&self) -> usize {
+        17
+    }
+
+    fn title(&self) -> &'static str {
+        "Number letter counts"
+    }
+
+    fn params(&self) -> Vec<Param> {
+        vec![
+            Param::new("begin", "First number to write out", BEGIN),
+            Param::new("end", "Last number to write out", END_INCLUSIVE),
+        ]
+    }
+
+    fn solve(&self) -> Answer {
+        solve(BEGIN, END_INCLUSIVE).into()
+    }
+
+    fn solve_with(&self, params: &Params) -> Answer {
+        solve(params.get("begin"), params.get("end")).into()
+    }
 }
 
 #[cfg(test)]
@@ -33,6 +33,8 @@ containing one-hundred rows; it cannot be solved by brute force, and requires a
 clever method! ;o)
 */
 
+use crate::problem::{Answer, Problem};
+
 fn solve(triangle: &[Vec<u64>]) -> u64 {
     let mut best_nums: Vec<u64> = triangle[0].clone();
     for row in triangle[1..].iter() {
@@ -84,9 +86,21 @@ const TRIANGLE_STRINGS: [&str; 15] = [
 
 // const EXAMPLE_TRIANGLE_STRINGS: [&str; 4] = ["3", "7, 4", "2, 4, 6", "8, 5, 9, 3"];
 
-pub fn main() {
-    println!("{}", solve(&parse_triangle_strings(&TRIANGLE_STRINGS)));
-    // println!("{}", solve(&parse_triangle_strings(&EXAMPLE_TRIANGLE_STRINGS)));
+pub struct Euler18;
+
+impl Problem for Euler18 {
+    fn number(&self) -> usize {
+        18
+    }
+
+    fn title(&self) -> &'static str {
+        "Maximum path sum I"
+    }
+
+    fn solve(&self) -> Answer {
+        solve(&parse_triangle_strings(&TRIANGLE_STRINGS)).into()
+        // solve(&parse_triangle_strings(&EXAMPLE_TRIANGLE_STRINGS)).into()
+    }
 }
 
 #[cfg(test)]
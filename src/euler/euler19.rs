@@ -17,6 +17,8 @@ Jan 1901 to 31 Dec 2000)?
 
 */
 
+use crate::problem::{Answer, Problem};
+
 use chrono::{Datelike, NaiveDate, Weekday};
 
 type Ymd = (i32, u32, u32);
@@ -76,8 +78,20 @@ const END: Ymd = (END_YEAR, END_MONTH, END_DAY);
 
 const WEEKDAY: Weekday = Weekday::Sun;
 
-pub fn main() {
-    println!("{}", solve(START, END, WEEKDAY));
+pub struct Euler19;
+
+impl Problem for Euler19 {
+    fn number(&self) -> usize {
+        19
+    }
+
+    fn title(&self) -> &'static str {
+        "Counting Sundays"
+    }
+
+    fn solve(&self) -> Answer {
+        solve(START, END, WEEKDAY).into()
+    }
 }
 
 #[cfg(test)]
@@ -8,13 +8,17 @@ By considering the terms in the Fibonacci sequence whose values do not exceed
 four million, find the sum of the even-valued terms.
 */
 
+use crate::params::{Param, Params};
+use crate::problem::{Answer, Problem};
+
+// The terms are u128s so that those just past a u64 limit don't overflow
 struct Fibonacci {
-    curr: u64,
-    next: u64,
+    curr: u128,
+    next: u128,
 }
 
 impl Iterator for Fibonacci {
-    type Item = u64;
+    type Item = u128;
 
     fn next(&mut self) -> Option<Self::Item> {
         let old_curr = self.curr;
@@ -31,25 +35,47 @@ impl Fibonacci {
     }
 }
 
-fn even(num: u64) -> bool {
+fn even(num: u128) -> bool {
     num % 2 == 0
 }
 
-fn solve(limit: u64) -> u64 {
+fn solve(limit: u64) -> u128 {
     Fibonacci::new()
         .filter(|num| even(*num))
-        .take_while(|num| *num < limit)
+        .take_while(|&num| num < u128::from(limit))
         .sum()
 }
 
 const LIMIT: u64 = 4_000_000;
 
-pub fn main() {
-    println!("{}", solve(LIMIT));
+pub struct Euler2;
+
+impl Problem for Euler2 {
+    fn number(&self) -> usize {
+        2
+    }
+
+    fn title(&self) -> &'static str {
+        "Even Fibonacci numbers"
+    }
+
+    fn params(&self) -> Vec<Param> {
+        vec![
+            Param::new("limit", "Sum even terms below this value", LIMIT),
+        ]
+    }
+
+    fn solve(&self) -> Answer {
+        solve(LIMIT).into()
+    }
+
+    fn solve_with(&self, params: &Params) -> Answer {
+        solve(params.get("limit")).into()
+    }
 }
 
 #[cfg(test)]
-const ANSWER: u64 = 4613732;
+const ANSWER: u128 = 4613732;
 
 #[test]
 fn test() {
@@ -9,6 +9,9 @@ and the sum of the digits in the number 10! is 3 + 6 + 2 + 8 + 8 + 0 + 0 =
 Find the sum of the digits in the number 100!
 */
 
+use crate::params::{Param, Params};
+use crate::problem::{Answer, Problem};
+
 use num_bigint::BigUint;
 use num_traits::One;
 
@@ -28,8 +31,30 @@ fn solve(num: u32) -> u64 {
 const INPUT: u32 = 100;
 const BASE: u32 = 10;
 
-pub fn main() {
-    println!("{}", solve(INPUT));
+pub struct Euler20;
+
+impl Problem for Euler20 {
+    fn number(&self) -> usize {
+        20
+    }
+
+    fn title(&self) -> &'static str {
+        "Factorial digit sum"
+    }
+
+    fn params(&self) -> Vec<Param> {
+        vec![
+            Param::new("n", "Sum the digits of n!", INPUT),
+        ]
+    }
+
+    fn solve(&self) -> Answer {
+        solve(INPUT).into()
+    }
+
+    fn solve_with(&self, params: &Params) -> Answer {
+        solve(params.get("n")).into()
+    }
 }
 
 #[cfg(test)]
@@ -10,123 +10,61 @@ and 110; therefore d(220) = 284. The proper divisors of 284 are 1, 2, 4, 71 and
 Evaluate the sum of all the amicable numbers under 10000.
 */
 
-use multiset::HashMultiSet;
-use std::collections::{HashMap, HashSet};
-use std::iter;
+use crate::number_theory::factorization::factorize;
+use crate::number_theory::linear_sieve::LinearSieve;
+use crate::params::{Param, Params};
+use crate::problem::{Answer, Problem};
+
+fn sum_of_proper_divisors(number: usize, sieve: &LinearSieve) -> usize {
+    let sum_of_divisors = if number <= sieve.limit() {
+        sieve.sigma(number) as usize
+    } else {
+        factorize(number).sigma(1)
+    };
+    sum_of_divisors - number
+}
 
-fn sum_of_proper_divisors(number: usize, primes: &[usize]) -> usize {
-    get_divisors(number, primes).iter().sum()
+fn is_amicable_number(number: usize, sieve: &LinearSieve) -> bool {
+    let other = sum_of_proper_divisors(number, sieve);
+    other != number && sum_of_proper_divisors(other, sieve) == number
 }
 
-fn sum_of_proper_divisors_naive(number: usize) -> usize {
-    (1..number)
-        .filter(|divisor| divisible_by(number, *divisor))
+fn solve(limit: usize) -> usize {
+    let sieve = LinearSieve::new(limit);
+    (2..limit)
+        .filter(|&number| is_amicable_number(number, &sieve))
         .sum()
 }
 
-fn get_divisors(number: usize, primes: &[usize]) -> HashSet<usize> {
-    let factor_multiset = get_factors(number, primes);
-    let factors_pairs = iter_counts(&factor_multiset)
-        .map(|(&factor, count)| (factor, count))
-        .collect::<Vec<(usize, usize)>>();
-    let mut divisors = factors_to_divisors(&factors_pairs);
-    divisors.remove(&number);
-    divisors
-}
+const LIMIT: usize = 10_000;
+// The sieve takes about 30 bytes per number, so this needs 1.5 GB
+const MAX_LIMIT: usize = 50_000_000;
 
-fn factors_to_divisors(factors: &[(usize, usize)]) -> HashSet<usize> {
-    let (&(factor, count), rest) = match factors.split_first() {
-        Some(pair) => pair,
-        None => return iter::once(1).collect(),
-    };
-    let rest_divisors = factors_to_divisors(rest);
-    (1..=count)
-        .flat_map(|num| {
-            let multiplier = factor.pow(num as u32);
-            rest_divisors
-                .iter()
-                .map(move |divisor| divisor * multiplier)
-        })
-        .chain(rest_divisors.iter().cloned())
-        .collect()
-}
+pub struct Euler21;
 
-fn get_factors(number: usize, primes: &[usize]) -> HashMultiSet<usize> {
-    if number < 2 {
-        return HashMultiSet::new();
-    }
-    let mut factors = HashMultiSet::new();
-    let mut number = number;
-    for &prime in primes {
-        while divisible_by(number, prime) {
-            factors.insert(prime);
-            number /= prime;
-        }
-        if number == 1 {
-            break;
-        }
-    }
-    if number > 1 {
-        factors.insert(number);
+impl Problem for Euler21 {
+    fn number(&self) -> usize {
+        21
     }
-    factors
-}
-
-fn divisible_by(number: usize, divisor: usize) -> bool {
-    number % divisor == 0
-}
 
-fn primes_below(limit: usize) -> Vec<usize> {
-    let mut is_composite = vec![false; limit];
-    let mut primes = Vec::new();
-    for num in 2..limit {
-        if is_composite[num] {
-            continue;
-        }
-        for index in (num..).step_by(num).take_while(|&x| x < limit) {
-            is_composite[index] = true;
-        }
-        primes.push(num);
+    fn title(&self) -> &'static str {
+        "Amicable numbers"
     }
-    primes
-}
-
-fn iter_counts<T: Eq + std::hash::Hash>(
-    multiset: &HashMultiSet<T>,
-) -> impl Iterator<Item = (&T, usize)> {
-    multiset
-        .distinct_elements()
-        .map(move |elem| (elem, multiset.count_of(elem)))
-}
 
-fn is_amicable_number(number: usize, map: &HashMap<usize, usize>) -> bool {
-    let other = map
-        .get(&number)
-        .cloned()
-        .unwrap_or_else(|| sum_of_proper_divisors_naive(number));
-    if number == other {
-        return false;
+    fn params(&self) -> Vec<Param> {
+        vec![
+            Param::new("limit", "Sum amicable numbers below this number", LIMIT)
+                .at_most(MAX_LIMIT),
+        ]
     }
-    map.get(&other)
-        .cloned()
-        .unwrap_or_else(|| sum_of_proper_divisors_naive(other))
-        == number
-}
-
-fn solve(limit: usize) -> usize {
-    let primes = primes_below(limit);
-    let map = (2..limit)
-        .map(|number| (number, sum_of_proper_divisors(number, &primes)))
-        .collect::<HashMap<_, _>>();
-    (2..limit)
-        .filter(|number| is_amicable_number(*number, &map))
-        .sum()
-}
 
-const LIMIT: usize = 10_000;
+    fn solve(&self) -> Answer {
+        solve(LIMIT).into()
+    }
 
-pub fn main() {
-    println!("{}", solve(LIMIT));
+    fn solve_with(&self, params: &Params) -> Answer {
+        solve(params.get("limit")).into()
+    }
 }
 
 #[cfg(test)]
@@ -11,6 +11,8 @@ obtain a score of 938 × 53 = 49714.
 What is the total of all the name scores in the file?
 */
 
+use crate::problem::{Answer, Problem};
+
 const LOWER_CASE_OFFSET: u8 = b'a' - 1;
 const UPPER_CASE_OFFSET: u8 = b'A' - 1;
 
@@ -48,8 +50,20 @@ fn solve(contents: &str) -> usize {
         .sum()
 }
 
-pub fn main() {
-    println!("{}", solve(FILE_CONTENTS));
+pub struct Euler22;
+
+impl Problem for Euler22 {
+    fn number(&self) -> usize {
+        22
+    }
+
+    fn title(&self) -> &'static str {
+        "Names scores"
+    }
+
+    fn solve(&self) -> Answer {
+        solve(FILE_CONTENTS).into()
+    }
 }
 
 #[cfg(test)]
@@ -18,91 +18,14 @@ Find the sum of all the positive integers which cannot be written as the sum of
 two abundant numbers.
 */
 
-use multiset::HashMultiSet;
-use std::collections::HashSet;
-use std::iter;
-
-fn get_divisors(number: usize, primes: &[usize]) -> HashSet<usize> {
-    let factor_multiset = get_factors(number, primes);
-    let factors_pairs = iter_counts(&factor_multiset)
-        .map(|(&factor, count)| (factor, count))
-        .collect::<Vec<(usize, usize)>>();
-    let mut divisors = factors_to_divisors(&factors_pairs);
-    divisors.remove(&number);
-    divisors
-}
+use crate::number_theory::linear_sieve::LinearSieve;
+use crate::problem::{Answer, Problem};
 
-fn factors_to_divisors(factors: &[(usize, usize)]) -> HashSet<usize> {
-    let (&(factor, count), rest) = match factors.split_first() {
-        Some(pair) => pair,
-        None => return iter::once(1).collect(),
-    };
-    let rest_divisors = factors_to_divisors(rest);
-    (1..=count)
-        .flat_map(|num| {
-            let multiplier = factor.pow(num as u32);
-            rest_divisors
-                .iter()
-                .map(move |divisor| divisor * multiplier)
-        })
-        .chain(rest_divisors.iter().cloned())
-        .collect()
-}
-
-fn get_factors(number: usize, primes: &[usize]) -> HashMultiSet<usize> {
-    if number < 2 {
-        return HashMultiSet::new();
-    }
-    let mut factors = HashMultiSet::new();
-    let mut number = number;
-    for &prime in primes {
-        while divisible_by(number, prime) {
-            factors.insert(prime);
-            number /= prime;
-        }
-        if number == 1 {
-            break;
-        }
-    }
-    if number > 1 {
-        factors.insert(number);
-    }
-    factors
-}
-
-fn divisible_by(number: usize, divisor: usize) -> bool {
-    number % divisor == 0
-}
-
-fn primes_below(limit: usize) -> Vec<usize> {
-    let mut is_composite = vec![false; limit];
-    let mut primes = Vec::new();
-    for num in 2..limit {
-        if is_composite[num] {
-            continue;
-        }
-        for index in (num..).step_by(num).take_while(|&x| x < limit) {
-            is_composite[index] = true;
-        }
-        primes.push(num);
-    }
-    primes
-}
-
-fn iter_counts<T: Eq + std::hash::Hash>(
-    multiset: &HashMultiSet<T>,
-) -> impl Iterator<Item = (&T, usize)> {
-    multiset
-        .distinct_elements()
-        .map(move |elem| (elem, multiset.count_of(elem)))
-}
-
-fn sum_of_divisors(num: usize, primes: &[usize]) -> usize {
-    get_divisors(num, primes).iter().sum::<usize>()
-}
+use std::collections::HashSet;
 
-fn is_abundant(num: usize, primes: &[usize]) -> bool {
-    sum_of_divisors(num, primes) > num
+fn is_abundant(num: usize, sieve: &LinearSieve) -> bool {
+    // The sum of the proper divisors is σ(n) - n
+    sieve.sigma(num) as usize > 2 * num
 }
 
 fn is_sum_of_abundant_nums(
@@ -117,9 +40,9 @@ fn is_sum_of_abundant_nums(
 }
 
 fn solve() -> usize {
-    let primes = primes_below(MAX_SUM_TWO_ABUNDANT_NUMS);
+    let sieve = LinearSieve::new(MAX_SUM_TWO_ABUNDANT_NUMS);
     let abundant_nums_vec = (1..=MAX_SUM_TWO_ABUNDANT_NUMS)
-        .filter(|num| is_abundant(*num, &primes))
+        .filter(|num| is_abundant(*num, &sieve))
         .collect::<Vec<_>>();
     let abundant_nums_set = abundant_nums_vec.iter().cloned().collect::<HashSet<_>>();
     (1..=MAX_SUM_TWO_ABUNDANT_NUMS)
@@ -129,8 +52,20 @@ fn solve() -> usize {
 
 const MAX_SUM_TWO_ABUNDANT_NUMS: usize = 28_123;
 
-pub fn main() {
-    println!("{}", solve());
+pub struct Euler23;
+
+impl Problem for Euler23 {
+    fn number(&self) -> usize {
+        23
+    }
+
+    fn title(&self) -> &'static str {
+        "Non-abundant sums"
+    }
+
+    fn solve(&self) -> Answer {
+        solve().into()
+    }
 }
 
 #[cfg(test)]
@@ -10,6 +10,9 @@ What is the millionth lexicographic permutation of the digits 0, 1, 2, 3, 4, 5,
 6, 7, 8 and 9?
 */
 
+use crate::params::{Param, Params};
+use crate::problem::{Answer, Problem};
+
 fn solve(nth: usize, min_num: u8, max_num: u8) -> usize {
     let index = nth - 1;
     let options = (min_num..=max_num).collect();
@@ -44,8 +47,32 @@ const NTH: usize = 1_000_000;
 const MIN_NUM: u8 = 0;
 const MAX_NUM: u8 = 9;
 
-pub fn main() {
-    println!("{}", solve(NTH, MIN_NUM, MAX_NUM));
+pub struct Euler24;
+
+impl Problem for Euler24 {
+    fn number(&self) -> usize {
+        24
+    }
+
+    fn title(&self) -> &'static str {
+        "Lexicographic permutations"
+    }
+
+    fn params(&self) -> Vec<Param> {
+        vec![
+            Param::new("nth", "Which permutation to find", NTH)
+                .at_least(1usize)
+                .at_most(factorial((MAX_NUM - MIN_NUM + 1) as usize)),
+        ]
+    }
+
+    fn solve(&self) -> Answer {
+        solve(NTH, MIN_NUM, MAX_NUM).into()
+    }
+
+    fn solve_with(&self, params: &Params) -> Answer {
+        solve(params.get("nth"), MIN_NUM, MAX_NUM).into()
+    }
 }
 
 #[cfg(test)]
@@ -24,20 +24,20 @@ What is the index of the first term in the Fibonacci sequence to contain 1000
 digits?
 */
 
+use crate::digits::Digits;
+use crate::params::{Param, Params};
+use crate::problem::{Answer, Problem};
+
 use num_bigint::BigUint;
 use num_traits::identities::One;
 
 fn solve(num_digits: usize) -> usize {
     let index = Fibonacci::new()
-        .take_while(|num| get_num_digits(num) < num_digits)
+        .take_while(|num| Digits::decimal(num.clone()).len() < num_digits)
         .count();
     index + 1
 }
 
-fn get_num_digits(num: &BigUint) -> usize {
-    num.to_str_radix(BASE).chars().count()
-}
-
 struct Fibonacci {
     prev: BigUint,
     next: BigUint,
@@ -63,11 +63,33 @@ impl Iterator for Fibonacci {
     }
 }
 
-const BASE: u32 = 10;
 const NUM_DIGITS: usize = 1_000;
 
-pub fn main() {
-    println!("{}", solve(NUM_DIGITS));
+pub struct Euler25;
+
+impl Problem for Euler25 {
+    fn number(&self) -> usize {
+        25
+    }
+
+    fn title(&self) -> &'static str {
+        "1000-digit Fibonacci number"
+    }
+
+    fn params(&self) -> Vec<Param> {
+        vec![
+            Param::new("num_digits", "Number of digits in the Fibonacci number", NUM_DIGITS)
+                .at_least(1usize),
+        ]
+    }
+
+    fn solve(&self) -> Answer {
+        solve(NUM_DIGITS).into()
+    }
+
+    fn solve_with(&self, params: &Params) -> Answer {
+        solve(params.get("num_digits")).into()
+    }
 }
 
 #[cfg(test)]
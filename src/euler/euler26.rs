@@ -19,126 +19,110 @@ Find the value of d < 1000 for which 1/d contains the longest recurring cycle in
 its decimal fraction part.
 */
 
-type Digit = u8;
-const DEFAULT_BASE: u32 = 10;
-
-#[derive(Debug, Clone, Eq, PartialEq)]
-struct Digits {
-    leading: Vec<Digit>,
-    repeating: Vec<Digit>,
-}
-
-pub fn main() {
-    println!("{}", solve());
-}
-
-fn solve() -> u32 {
-    const MAX: u32 = 1000;
-    (1..MAX)
-        .max_by_key(|d| Digits::one_over(*d).repeating.len())
-        .unwrap()
-}
-
-impl Digits {
-    fn new(leading: Vec<Digit>, repeating: Vec<Digit>) -> Self {
-        Digits { leading, repeating }
+use crate::number_theory::divisible_by;
+use crate::number_theory::order::multiplicative_order;
+use crate::params::{Param, Params};
+use crate::problem::{Answer, Problem};
+
+/// The length of the recurring cycle of 1/d in base 10. Dividing out the
+/// factors of 2 and 5, which only affect the digits before the cycle, leaves
+/// d' coprime to 10, and 10^k ≡ 1 (mod d') first when k is the cycle length.
+/// If d' is 1, the decimal terminates instead.
+fn cycle_length(mut d: u64) -> u64 {
+    for p in [2, 5] {
+        while divisible_by(d, p) {
+            d /= p;
+        }
     }
-
-    fn one_over(den: u32) -> Digits {
-        Digits::one_over_base(den, DEFAULT_BASE)
+    match d {
+        1 => 0,
+        d => multiplicative_order(10, d).expect("d is coprime to 10"),
     }
+}
 
-    fn one_over_base(den: u32, base: u32) -> Digits {
-        if den == 0 {
-            panic!("Must be greater than one")
+fn solve(limit: u64) -> u64 {
+    // The cycle of 1/d is at most d - 1 digits long, so only the largest
+    // denominators need checking before nothing smaller can beat the best
+    let (mut best, mut best_length) = (0, 0);
+    for d in (1..limit).rev() {
+        if d - 1 <= best_length {
+            break;
         }
-        let mut digits_rems: Vec<(Digit, u32)> = Vec::new();
-        let mut num = base;
-        loop {
-            if num == 0 {
-                return Digits::new(from_digits_rems(&digits_rems), Vec::new());
-            }
-            let (quot, rem) = (num / den, num % den);
-            let quot = quot as Digit;
-            num = rem * base;
-            if let Some(index) = digits_rems.iter().position(|&(_, r)| r == rem) {
-                let (match_digit, _) = digits_rems[index];
-                return if match_digit == quot {
-                    Digits::new(
-                        from_digits_rems(&digits_rems[..index]),
-                        from_digits_rems(&digits_rems[index..]),
-                    )
-                } else {
-                    digits_rems.push((quot, rem));
-                    Digits::new(
-                        from_digits_rems(&digits_rems[..index + 1]),
-                        from_digits_rems(&digits_rems[index + 1..]),
-                    )
-                };
-            }
-            digits_rems.push((quot, rem));
+        let length = cycle_length(d);
+        if length > best_length {
+            (best, best_length) = (d, length);
         }
     }
+    best
 }
 
-fn from_digits_rems(digits_rems: &[(Digit, u32)]) -> Vec<Digit> {
-    digits_rems.iter().map(|&(d, _)| d).collect()
-}
+const LIMIT: u64 = 1000;
 
-#[cfg(test)]
-mod test {
-    use super::*;
+pub struct Euler26;
 
-    #[test]
-    fn two() {
-        assert_eq!(Digits::new(vec![5], vec![]), Digits::one_over(2));
+impl Problem for Euler26 {
+    fn number(&self) -> usize {
+        26
     }
 
-    #[test]
-    fn three() {
-        assert_eq!(Digits::new(vec![], vec![3]), Digits::one_over(3));
+    fn title(&self) -> &'static str {
+        "Reciprocal cycles"
     }
 
-    #[test]
-    fn four() {
-        assert_eq!(Digits::new(vec![2, 5], vec![]), Digits::one_over(4));
+    fn params(&self) -> Vec<Param> {
+        vec![
+            Param::new("limit", "Consider denominators below this number", LIMIT),
+        ]
     }
 
-    #[test]
-    fn five() {
-        assert_eq!(Digits::new(vec![2], vec![]), Digits::one_over(5));
+    fn solve(&self) -> Answer {
+        solve(LIMIT).into()
     }
 
-    #[test]
-    fn six() {
-        assert_eq!(Digits::new(vec![1], vec![6]), Digits::one_over(6));
+    fn solve_with(&self, params: &Params) -> Answer {
+        solve(params.get("limit")).into()
     }
+}
 
-    #[test]
-    fn seven() {
-        assert_eq!(
-            Digits::new(vec![], vec![1, 4, 2, 8, 5, 7]),
-            Digits::one_over(7)
-        );
-    }
+#[cfg(test)]
+mod test {
+    use super::*;
 
-    #[test]
-    fn eight() {
-        assert_eq!(Digits::new(vec![1, 2, 5], vec![]), Digits::one_over(8));
+    /// The cycle length found by long division, waiting for a remainder to
+    /// repeat.
+    fn cycle_length_naive(d: u64) -> u64 {
+        let mut seen = vec![None; d as usize];
+        let mut remainder = 1 % d;
+        for position in 0u64.. {
+            if remainder == 0 {
+                return 0;
+            }
+            if let Some(start) = seen[remainder as usize] {
+                return position - start;
+            }
+            seen[remainder as usize] = Some(position);
+            remainder = remainder * 10 % d;
+        }
+        unreachable!()
     }
 
     #[test]
-    fn nine() {
-        assert_eq!(Digits::new(vec![], vec![1]), Digits::one_over(9));
+    fn small() {
+        let lengths = [0, 1, 0, 0, 1, 6, 0, 1, 0];
+        for (d, &length) in (2..=10).zip(lengths.iter()) {
+            assert_eq!(length, cycle_length(d), "1/{}", d);
+        }
     }
 
     #[test]
-    fn ten() {
-        assert_eq!(Digits::new(vec![1], vec![]), Digits::one_over(10));
+    fn matches_long_division() {
+        for d in 1..2_000 {
+            assert_eq!(cycle_length_naive(d), cycle_length(d), "1/{}", d);
+        }
     }
 
     #[test]
     fn answer() {
-        assert_eq!(983, solve());
+        assert_eq!(983, solve(LIMIT));
     }
 }
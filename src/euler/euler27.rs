@@ -20,23 +20,34 @@ product of the coefficients, a and b, for the quadratic expression that produces
 the maximum number of primes for consecutive values of n, starting with n=0.
 */
 
-use primal::Sieve;
+use crate::number_theory::primality::is_prime;
+use crate::problem::{Answer, Problem};
+
 use std::convert::TryFrom;
 
-pub fn main() {
-    println!("{}", solve());
+pub struct Euler27;
+
+impl Problem for Euler27 {
+    fn number(&self) -> usize {
+        27
+    }
+
+    fn title(&self) -> &'static str {
+        "Quadratic primes"
+    }
+
+    fn solve(&self) -> Answer {
+        solve().into()
+    }
 }
 
 fn solve() -> Num {
     const MAX: Num = 1000;
     let a_iter = (-MAX..MAX).filter(|&n| n != 0);
     let b_iter = -MAX..MAX;
-    // n^2 + a*n + b where n = a = b = MAX
-    const MAX_NUM: Num = MAX * MAX + MAX * MAX + MAX;
-    let sieve = Sieve::new(MAX_NUM as usize);
     let formula = iproduct!(a_iter, b_iter)
         .map(Formula::from_tuple)
-        .max_by_key(|f| f.count_primes(&sieve))
+        .max_by_key(Formula::count_primes)
         .unwrap();
     formula.a * formula.b
 }
@@ -65,9 +76,7 @@ impl Formula {
             .flat_map(u64::try_from)
     }
 
-    fn count_primes(&self, sieve: &Sieve) -> usize {
-        self.nums()
-            .take_while(|&n| sieve.is_prime(n as usize))
-            .count()
+    fn count_primes(&self) -> usize {
+        self.nums().take_while(|&n| is_prime(n)).count()
     }
 }
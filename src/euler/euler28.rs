@@ -14,12 +14,42 @@ What is the sum of the numbers on the diagonals in a 1001 by 1001 spiral formed
 in the same way?
 */
 
+use crate::params::{Param, Params};
+use crate::problem::{Answer, Problem};
+
 const WIDTH: Num = 1001;
 
-type Num = u64;
+type Num = u128;
+
+pub struct Euler28;
+
+impl Problem for Euler28 {
+    fn number(&self) -> usize {
+        28
+    }
+
+    fn title(&self) -> &'static str {
+        "Number spiral diagonals"
+    }
 
-pub fn main() {
-    println!("{}", sum_diagonals(WIDTH).unwrap());
+    fn params(&self) -> Vec<Param> {
+        vec![
+            Param::new("width", "Width of the spiral, which must be odd", WIDTH).check(
+                |&width: &Num| match width % 2 {
+                    1 => Ok(()),
+                    _ => Err(String::from("must be odd")),
+                },
+            ),
+        ]
+    }
+
+    fn solve(&self) -> Answer {
+        sum_diagonals(WIDTH).unwrap().into()
+    }
+
+    fn solve_with(&self, params: &Params) -> Answer {
+        sum_diagonals(params.get("width")).expect("width was checked").into()
+    }
 }
 
 fn sum_diagonals(final_width: Num) -> Option<Num> {
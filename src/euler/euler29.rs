@@ -15,6 +15,8 @@ How many distinct terms are in the sequence generated by a^b for 2 ≤ a ≤ 100
 2 ≤ b ≤ 100?
 */
 
+use crate::problem::{Answer, Problem};
+
 use num_bigint::BigUint;
 use num_traits::Pow;
 use std::collections::HashSet;
@@ -22,8 +24,20 @@ use std::collections::HashSet;
 const A_RANGE: (u64, u64) = (2, 100);
 const B_RANGE: (u32, u32) = (2, 100);
 
-pub fn main() {
-    println!("{}", num_distinct_terms(A_RANGE, B_RANGE));
+pub struct Euler29;
+
+impl Problem for Euler29 {
+    fn number(&self) -> usize {
+        29
+    }
+
+    fn title(&self) -> &'static str {
+        "Distinct powers"
+    }
+
+    fn solve(&self) -> Answer {
+        num_distinct_terms(A_RANGE, B_RANGE).into()
+    }
 }
 
 fn num_distinct_terms(a_range: (u64, u64), b_range: (u32, u32)) -> usize {
@@ -4,64 +4,47 @@ The prime factors of 13195 are 5, 7, 13 and 29.
 What is the largest prime factor of the number 600851475143?
 */
 
-fn primes_below(limit: usize) -> Vec<usize> {
-    let mut is_composite = vec![false; limit];
-    let mut primes = Vec::new();
-    for num in 2..limit {
-        if is_composite[num] {
-            continue;
-        }
-        for index in (num..).step_by(num).take_while(|&x| x < limit) {
-            is_composite[index] = true;
-        }
-        primes.push(num);
-    }
-    primes
+use crate::number_theory::factorization::factorize;
+use crate::params::{Param, Params};
+use crate::problem::{Answer, Problem};
+
+fn solve(number: u128) -> u128 {
+    factorize(number)
+        .primes()
+        .next_back()
+        .expect("no prime factors")
 }
 
-fn get_factors(number: usize) -> Vec<usize> {
-    let max_factor = (number as f64).sqrt() as usize;
-    if number < 2 {
-        return Vec::new();
-    }
-    let mut factors = Vec::new();
-    let primes = primes_below(max_factor + 1);
-    let mut number = number;
-    for prime in primes {
-        while divisible_by(number, prime) {
-            factors.push(prime);
-            number /= prime;
-        }
-        if number == 1 {
-            break;
-        }
-    }
-    if number > 1 {
-        factors.push(number);
+const NUMBER: u128 = 600_851_475_143;
+
+pub struct Euler3;
+
+impl Problem for Euler3 {
+    fn number(&self) -> usize {
+        3
     }
-    factors
-}
 
-fn divisible_by(number: usize, divisor: usize) -> bool {
-    number % divisor == 0
-}
+    fn title(&self) -> &'static str {
+        "Largest prime factor"
+    }
 
-fn solve(number: usize) -> usize {
-    get_factors(number)
-        .iter()
-        .max()
-        .copied()
-        .expect("no prime factors")
-}
+    fn params(&self) -> Vec<Param> {
+        vec![
+            Param::new("number", "Find the largest prime factor of this number", NUMBER).at_least(2u128),
+        ]
+    }
 
-const NUMBER: usize = 600_851_475_143;
+    fn solve(&self) -> Answer {
+        solve(NUMBER).into()
+    }
 
-pub fn main() {
-    println!("{:?}", solve(NUMBER));
+    fn solve_with(&self, params: &Params) -> Answer {
+        solve(params.get("number")).into()
+    }
 }
 
 #[cfg(test)]
-const ANSWER: usize = 6857;
+const ANSWER: u128 = 6857;
 
 #[test]
 fn test() {
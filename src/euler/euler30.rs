@@ -14,19 +14,59 @@ of their digits.
 */
 
 use crate::digits;
+use crate::params::{Param, Params};
+use crate::problem::{Answer, Problem};
 
 use digits::Digits;
 
-pub fn main() {
-    println!("{}", solve(5));
+const POWER: u32 = 5;
+// Past this, the largest number worth checking overflows a u64
+const MAX_POWER: u32 = 18;
+
+pub struct Euler30;
+
+impl Problem for Euler30 {
+    fn number(&self) -> usize {
+        30
+    }
+
+    fn title(&self) -> &'static str {
+        "Digit fifth powers"
+    }
+
+    fn params(&self) -> Vec<Param> {
+        vec![
+            Param::new("power", "Power to raise each digit to", POWER).at_most(MAX_POWER),
+        ]
+    }
+
+    fn solve(&self) -> Answer {
+        solve(POWER).into()
+    }
+
+    fn solve_with(&self, params: &Params) -> Answer {
+        solve(params.get("power")).into()
+    }
 }
 
 fn solve(power: u32) -> u64 {
-    (2..1_000_000)
+    (2..=search_limit(power))
         .filter(|&n| is_sum_of_powered_digits(n, power))
         .sum()
 }
 
+// A number with d digits is at least 10^(d - 1), but its digits' powers sum to
+// at most d * 9^power, so only numbers up to that sum for the longest d where
+// it still reaches 10^(d - 1) can be sums of their digits' powers
+fn search_limit(power: u32) -> u64 {
+    let max_sum = |num_digits: u32| u64::from(num_digits) * 9u64.pow(power);
+    let num_digits = (1..)
+        .take_while(|&num_digits| max_sum(num_digits) >= 10u64.pow(num_digits - 1))
+        .last()
+        .expect("one digit numbers always qualify");
+    max_sum(num_digits)
+}
+
 fn is_sum_of_powered_digits(num: u64, power: u32) -> bool {
     let sum: u64 = Digits::decimal(num)
         .map(|digit| u64::from(digit).pow(power))
@@ -47,4 +87,13 @@ mod test {
     fn answer() {
         assert_eq!(solve(5), 443_839);
     }
+
+    #[test]
+    fn other_powers() {
+        assert_eq!(solve(0), 0);
+        assert_eq!(solve(1), 44);
+        assert_eq!(solve(3), 153 + 370 + 371 + 407);
+        assert_eq!(search_limit(5), 354_294);
+        assert_eq!(search_limit(MAX_POWER), 19 * 9u64.pow(MAX_POWER));
+    }
 }
@@ -11,13 +11,27 @@ It is possible to make £2 in the following way:
 How many different ways can £2 be made using any number of coins?
 */
 
+use crate::problem::{Answer, Problem};
+
 type Num = u32;
 
 const COINS: [Num; 8] = [200, 100, 50, 20, 10, 5, 2, 1];
 const TARGET: Num = 200;
 
-pub fn main() {
-    println!("{}", solve());
+pub struct Euler31;
+
+impl Problem for Euler31 {
+    fn number(&self) -> usize {
+        31
+    }
+
+    fn title(&self) -> &'static str {
+        "Coin sums"
+    }
+
+    fn solve(&self) -> Answer {
+        solve().into()
+    }
 }
 
 fn solve() -> usize {
@@ -14,14 +14,27 @@ include it once in your sum.
 */
 
 use crate::digits;
+use crate::problem::{Answer, Problem};
 
 use digits::Digits;
 use std::collections::HashSet;
 
 type Num = u64;
 
-pub fn main() {
-    println!("{}", solve());
+pub struct Euler32;
+
+impl Problem for Euler32 {
+    fn number(&self) -> usize {
+        32
+    }
+
+    fn title(&self) -> &'static str {
+        "Pandigital products"
+    }
+
+    fn solve(&self) -> Answer {
+        solve().into()
+    }
 }
 
 fn solve() -> Num {
@@ -5,6 +5,9 @@ the product of two 2-digit numbers is 9009 = 91 × 99.
 Find the largest palindrome made from the product of two 3-digit numbers.
  */
 
+use crate::params::{Param, Params};
+use crate::problem::{Answer, Problem};
+
 fn number_is_palindrome(number: u32) -> bool {
     is_palindrome(&number.to_string())
 }
@@ -38,9 +41,35 @@ fn solve(num_digits: u32) -> u32 {
 }
 
 const NUM_DIGITS: u32 = 3;
+// The product of two five-digit numbers can overflow a u32
+const MAX_NUM_DIGITS: u32 = 4;
+
+pub struct Euler4;
+
+impl Problem for Euler4 {
+    fn number(&self) -> usize {
+        4
+    }
+
+    fn title(&self) -> &'static str {
+        "Largest palindrome product"
+    }
 
-pub fn main() {
-    println!("{}", solve(NUM_DIGITS));
+    fn params(&self) -> Vec<Param> {
+        vec![
+            Param::new("num_digits", "Number of digits in each factor", NUM_DIGITS)
+                .at_least(1u32)
+                .at_most(MAX_NUM_DIGITS),
+        ]
+    }
+
+    fn solve(&self) -> Answer {
+        solve(NUM_DIGITS).into()
+    }
+
+    fn solve_with(&self, params: &Params) -> Answer {
+        solve(params.get("num_digits")).into()
+    }
 }
 
 #[cfg(test)]
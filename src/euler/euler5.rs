@@ -6,63 +6,45 @@ What is the smallest positive number that is evenly divisible by all of the
 numbers from 1 to 20?
  */
 
-use multiset::HashMultiSet;
+use crate::number_theory::factorization::{factorize, Factorization};
+use crate::params::{Param, Params};
+use crate::problem::{Answer, Problem};
 
-fn primes_below(limit: usize) -> Vec<usize> {
-    let mut is_composite = vec![false; limit];
-    let mut primes = Vec::new();
-    for num in 2..limit {
-        if is_composite[num] {
-            continue;
-        }
-        for index in (num..).step_by(num).take_while(|&x| x < limit) {
-            is_composite[index] = true;
-        }
-        primes.push(num);
-    }
-    primes
+fn solve(limit: usize) -> usize {
+    (1..=limit)
+        .map(factorize)
+        .fold(Factorization::one(), |lcm, factors| lcm.lcm(&factors))
+        .value()
 }
 
-fn get_factors(number: usize) -> HashMultiSet<usize> {
-    let max_factor = (number as f64).sqrt() as usize;
-    if number < 2 {
-        return HashMultiSet::new();
-    }
-    let mut factors = HashMultiSet::new();
-    let primes = primes_below(max_factor + 1);
-    let mut number = number;
-    for prime in primes {
-        while divisible_by(number, prime) {
-            factors.insert(prime);
-            number /= prime;
-        }
-        if number == 1 {
-            break;
-        }
-    }
-    if number > 1 {
-        factors.insert(number);
+const LIMIT: usize = 20;
+// The least common multiple of 1 to 47 overflows a u64
+const MAX_LIMIT: usize = 46;
+
+pub struct Euler5;
+
+impl Problem for Euler5 {
+    fn number(&self) -> usize {
+        5
     }
-    factors
-}
 
-fn divisible_by(number: usize, divisor: usize) -> bool {
-    number % divisor == 0
-}
+    fn title(&self) -> &'static str {
+        "Smallest multiple"
+    }
 
-fn solve(limit: usize) -> usize {
-    let all_factors = (2..=limit)
-        .map(get_factors)
-        .fold(HashMultiSet::new(), |acc, factors| {
-            acc.union(&factors).cloned().collect()
-        });
-    all_factors.iter().product()
-}
+    fn params(&self) -> Vec<Param> {
+        vec![
+            Param::new("limit", "Divisible by every number from 1 to this one", LIMIT).at_most(MAX_LIMIT),
+        ]
+    }
 
-const LIMIT: usize = 20;
+    fn solve(&self) -> Answer {
+        solve(LIMIT).into()
+    }
 
-pub fn main() {
-    println!("{}", solve(LIMIT));
+    fn solve_with(&self, params: &Params) -> Answer {
+        solve(params.get("limit")).into()
+    }
 }
 
 #[cfg(test)]
@@ -14,6 +14,9 @@ Find the difference between the sum of the squares of the first one hundred
 natural numbers and the square of the sum.
 */
 
+use crate::params::{Param, Params};
+use crate::problem::{Answer, Problem};
+
 fn sum_of_squares(limit: u64) -> u64 {
     let n = limit;
     (n * (n + 1) * (2 * n + 1)) / 6
@@ -30,9 +33,33 @@ fn solve(limit: u64) -> u64 {
 }
 
 const LIMIT: u64 = 100;
+// The square of the sum of the first 92,682 numbers overflows a u64
+const MAX_LIMIT: u64 = 92_681;
+
+pub struct Euler6;
+
+impl Problem for Euler6 {
+    fn number(&self) -> usize {
+        6
+    }
+
+    fn title(&self) -> &'static str {
+        "Sum square difference"
+    }
+
+    fn params(&self) -> Vec<Param> {
+        vec![
+            Param::new("limit", "How many natural numbers to include", LIMIT).at_most(MAX_LIMIT),
+        ]
+    }
+
+    fn solve(&self) -> Answer {
+        solve(LIMIT).into()
+    }
 
-pub fn main() {
-    println!("{}", solve(LIMIT));
+    fn solve_with(&self, params: &Params) -> Answer {
+        solve(params.get("limit")).into()
+    }
 }
 
 #[cfg(test)]
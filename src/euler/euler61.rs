@@ -30,6 +30,9 @@ each polygonal type: triangle, square, pentagonal, hexagonal, heptagonal, and
 octagonal, is represented by a different number in the set.
 */
 
+use crate::diagnostics::diagnostic;
+use crate::problem::{Answer, Problem};
+
 use enumset::{EnumSet, EnumSetType};
 use lazy_static::lazy_static;
 use std::collections::{HashMap, HashSet};
@@ -40,8 +43,8 @@ const BASE: u8 = 10;
 const BASE_NUM: Num = BASE as Num;
 const NUM_DIGITS: usize = 4;
 lazy_static! {
-    static ref MIN_NUM: Num = int_pow(BASE_NUM, NUM_DIGITS - 1);
-    static ref MAX_NUM: Num = int_pow(BASE_NUM, NUM_DIGITS);
+    static ref MIN_NUM: Num = BASE_NUM.pow(NUM_DIGITS as u32 - 1);
+    static ref MAX_NUM: Num = BASE_NUM.pow(NUM_DIGITS as u32);
 }
 
 #[derive(EnumSetType, Debug)]
@@ -77,7 +80,23 @@ impl NumExtra {
     }
 }
 
-pub fn main() {
+pub struct Euler61;
+
+impl Problem for Euler61 {
+    fn number(&self) -> usize {
+        61
+    }
+
+    fn title(&self) -> &'static str {
+        "Cyclical figurate numbers"
+    }
+
+    fn solve(&self) -> Answer {
+        solve().into()
+    }
+}
+
+fn solve() -> Num {
     let mut nums: HashMap<Num, NumExtra> = HashMap::new();
     for figurate in EnumSet::all() {
         for num in figurate_nums(NUM_DIGITS, figurate) {
@@ -95,8 +114,7 @@ pub fn main() {
             .push(num_extra.clone());
     }
     let cycle = find_figurate_cycle(&by_leading_digits).expect("No solution found");
-    let sum: Num = cycle.into_iter().sum();
-    println!("{}", sum);
+    cycle.into_iter().sum()
 }
 
 fn find_figurate_cycle(by_leading_digits: &HashMap<Num, Vec<NumExtra>>) -> Option<Vec<Num>> {
@@ -136,7 +154,7 @@ fn find_figurate_cycle_helper(
                     if result.is_some() {
                         let cycle_string =
                             cycle.iter().map(|ne| (ne.0.num, ne.1)).collect::<Vec<_>>();
-                        eprintln!("Cycle: {:?}", cycle_string);
+                        diagnostic!("Cycle: {:?}", cycle_string);
                         return result;
                     }
                     seen_figurates.remove(figurate);
@@ -206,10 +224,6 @@ fn get_digits(x: Num) -> Option<[u8; NUM_DIGITS]> {
     Some(digits)
 }
 
-fn int_pow(base: Num, power: Num) -> Num {
-    (0..power).fold(1, |result, _| result * base)
-}
-
 fn triangle_num(n: Num) -> Num {
     n * (n + 1) / 2
 }
@@ -246,8 +260,8 @@ fn figurate_num(n: Num, figurate: Figurate) -> Num {
 }
 
 fn figurate_nums(num_digits: usize, figurate: Figurate) -> impl Iterator<Item = Num> {
-    let min = int_pow(BASE_NUM, num_digits - 1);
-    let max = int_pow(BASE_NUM, num_digits);
+    let min = BASE_NUM.pow(num_digits as u32 - 1);
+    let max = BASE_NUM.pow(num_digits as u32);
     (1..)
         .map(move |n| figurate_num(n, figurate))
         .skip_while(move |n| n < &min)
@@ -270,54 +284,6 @@ fn figurate_nums(num_digits: usize, figurate: Figurate) -> impl Iterator<Item =
 mod test {
     use super::*;
 
-    #[test]
-    fn test_int_pow_special_case_zero_to_the_zero() {
-        assert_eq!(int_pow(0, 0), 1);
-    }
-
-    #[test]
-    fn test_int_pow_base_zero() {
-        for power in 1..4 {
-            assert_eq!(int_pow(0, power), 0);
-        }
-    }
-
-    #[test]
-    fn test_int_pow_power_zero() {
-        for base in 0..4 {
-            assert_eq!(int_pow(base, 0), 1);
-        }
-    }
-
-    #[test]
-    fn test_int_pow_base_one() {
-        for power in 0..4 {
-            assert_eq!(int_pow(1, power), 1);
-        }
-    }
-
-    #[test]
-    fn test_int_pow_power_one() {
-        for base in 0..4 {
-            assert_eq!(int_pow(base, 1), base);
-        }
-    }
-
-    #[test]
-    fn test_int_pow_normal() {
-        let test_cases = [
-            (2, 2, 4),
-            (2, 3, 8),
-            (2, 4, 16),
-            (3, 2, 9),
-            (3, 3, 27),
-            (3, 4, 81),
-        ];
-        for &(base, power, result) in test_cases.iter() {
-            assert_eq!(int_pow(base, power), result);
-        }
-    }
-
     #[test]
     fn test_get_digits() {
         let test_cases = [
@@ -5,32 +5,62 @@ The cube, 41063625 (345^3), can be permuted to produce two other cubes: 56623104
 Find the smallest cube for which exactly five permutations of its digits are cube.
  */
 
+use crate::number_theory::roots::icbrt;
+use crate::params::{Param, Params};
+use crate::problem::{Answer, Problem};
+
 use counter::Counter;
 use std::collections::HashMap;
 
 const BASE: usize = 10;
 const NUM_PERMUTATIONS: usize = 5;
+// The smallest number with 20 digits overflows a u64
+const MAX_NUM_DIGITS: usize = 19;
 
 type DigitCounts = [usize; BASE];
 
-pub fn main() {
-    for num_digits in NUM_PERMUTATIONS.. {
-        if let Some(permutations) = find_cube_permutations(num_digits, NUM_PERMUTATIONS) {
-            // eprintln!("{:?}", permutations);
-            let smallest = permutations.into_iter().min().expect("Empty permutations");
-            println!("{}", smallest);
-            break;
-        }
+pub struct Euler62;
+
+impl Problem for Euler62 {
+    fn number(&self) -> usize {
+        62
+    }
+
+    fn title(&self) -> &'static str {
+        "Cubic permutations"
+    }
+
+    fn params(&self) -> Vec<Param> {
+        vec![
+            Param::new("num_permutations", "Number of permutations that must be cubes", NUM_PERMUTATIONS)
+                .at_least(1usize)
+                .at_most(MAX_NUM_DIGITS),
+        ]
+    }
+
+    fn solve(&self) -> Answer {
+        solve(NUM_PERMUTATIONS).into()
+    }
+
+    fn solve_with(&self, params: &Params) -> Answer {
+        solve(params.get("num_permutations")).into()
     }
 }
 
+fn solve(num_permutations: usize) -> usize {
+    let permutations = (num_permutations..=MAX_NUM_DIGITS)
+        .find_map(|num_digits| find_cube_permutations(num_digits, num_permutations))
+        .expect("no solution found");
+    // eprintln!("{:?}", permutations);
+    permutations.into_iter().min().expect("Empty permutations")
+}
+
 fn find_cubes(num_digits: usize) -> impl Iterator<Item = usize> {
-    let min = int_pow(BASE, num_digits - 1);
-    let max = int_pow(BASE, num_digits);
-    (1..)
-        .map(cube)
-        .skip_while(move |&n| n < min)
-        .take_while(move |&n| n < max)
+    // The cubes with num_digits digits are those of the numbers from just
+    // above ∛(min - 1) up to ∛(max - 1)
+    let min = BASE.pow(num_digits as u32 - 1) as u64;
+    let max = BASE.pow(num_digits as u32) as u64;
+    (icbrt(min - 1) + 1..=icbrt(max - 1)).map(|n| cube(n as usize))
 }
 
 fn find_cube_permutations(num_digits: usize, num_permutations: usize) -> Option<Vec<usize>> {
@@ -72,10 +102,6 @@ fn cube(num: usize) -> usize {
     num * num * num
 }
 
-fn int_pow(base: usize, power: usize) -> usize {
-    (0..power).fold(1, |result, _| result * base)
-}
-
 #[cfg(test)]
 mod test {
     use super::*;
@@ -98,6 +124,17 @@ mod test {
         assert_eq!(expected, actual);
     }
 
+    #[test]
+    fn test_params() {
+        let params = |value: &str| {
+            let overrides = [format!("num_permutations={}", value).parse().unwrap()];
+            Params::new(Euler62.params(), &overrides)
+        };
+        assert!(params("19").is_ok());
+        assert!(params("0").is_err());
+        assert!(params("20").is_err());
+    }
+
     #[test]
     fn test_get_digits() {
         let digit_counts = get_digits(4827448);
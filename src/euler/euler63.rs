@@ -4,11 +4,10 @@ The 5-digit number, 16807=7^5, is also a fifth power. Similarly, the 9-digit num
 How many n-digit positive integers exist which are also an nth power?
  */
 
-use num_bigint::BigUint;
-use num_traits::Zero;
-use std::ops::DivAssign;
+use crate::digits::Digits;
+use crate::problem::{Answer, Problem};
 
-const DIGIT_BASE: u32 = 10;
+use num_bigint::BigUint;
 
 // 10 ^ 1 has 2 digits
 // 10 ^ 2 has 3 digits
@@ -16,55 +15,42 @@ const DIGIT_BASE: u32 = 10;
 // Any base greater than 10 will always have more digits than the exponent
 const MAX_BASE: u32 = 10;
 
-pub fn main() {
-    let answer = (0..MAX_BASE)
+pub struct Euler63;
+
+impl Problem for Euler63 {
+    fn number(&self) -> usize {
+        63
+    }
+
+    fn title(&self) -> &'static str {
+        "Powerful digit counts"
+    }
+
+    fn solve(&self) -> Answer {
+        solve().into()
+    }
+}
+
+fn solve() -> usize {
+    // Only positive numbers count, so 0 = 0^1 doesn't
+    (1..MAX_BASE)
         .map(BigUint::from)
         .flat_map(get_matching_power_num_digits)
-        .count();
-    println!("{}", answer);
+        .count()
 }
 
 fn get_matching_power_num_digits(base: BigUint) -> impl Iterator<Item = u32> {
     (0..)
-        .map(move |power| (power, num_digits(base.pow(power))))
+        .map(move |power| (power, Digits::decimal(base.pow(power)).len() as u32))
         .skip_while(|(power, num_digits)| power < num_digits)
         .take_while(|(power, num_digits)| power == num_digits)
         .map(|(power, _)| power)
 }
 
-fn num_digits(mut num: BigUint) -> u32 {
-    let mut count = 0;
-    while num > BigUint::zero() {
-        num.div_assign(DIGIT_BASE);
-        count += 1;
-    }
-    count
-}
-
 #[cfg(test)]
 mod test {
     use super::*;
 
-    #[test]
-    fn test_num_digits() {
-        assert_eq!(1, num_digits(1));
-        assert_eq!(1, num_digits(2));
-        assert_eq!(1, num_digits(9));
-        assert_eq!(2, num_digits(10));
-        assert_eq!(2, num_digits(11));
-        assert_eq!(2, num_digits(50));
-        assert_eq!(2, num_digits(99));
-        assert_eq!(3, num_digits(100));
-        assert_eq!(3, num_digits(101));
-        assert_eq!(5, num_digits(12345));
-        assert_eq!(8, num_digits(12345678));
-        assert_eq!(13, num_digits(1234567890123));
-    }
-
-    fn num_digits(num: usize) -> u32 {
-        super::num_digits(BigUint::from(num))
-    }
-
     #[test]
     fn test_get_matching_power_num_digits() {
         let actual: Vec<_> = get_matching_power_num_digits(2).collect();
@@ -27,28 +27,45 @@ Exactly four continued fractions, for N <= 13, have an odd period.
 How many continued fractions for N <= 10,000 have an odd period?
  */
 
-use std::cmp::Ordering;
+use crate::diagnostics::diagnostic;
+use crate::number_theory::roots::isqrt;
+use crate::problem::{Answer, Problem};
+
 use std::collections::HashMap;
 
 const MAX: u32 = 10_000;
 
-pub fn main() {
-    let num_odd_periods = (2..=MAX)
+pub struct Euler64;
+
+impl Problem for Euler64 {
+    fn number(&self) -> usize {
+        64
+    }
+
+    fn title(&self) -> &'static str {
+        "Odd period square roots"
+    }
+
+    fn solve(&self) -> Answer {
+        solve().into()
+    }
+}
+
+fn solve() -> usize {
+    (2..=MAX)
         .map(expanded_sqrt)
         .filter_map(|sqrt| match sqrt {
             SqrtExpansionResult::Expanded(expanded) => Some(expanded),
             _ => None,
         })
-        .map(|expanded| {
+        .inspect(|expanded| {
             if expanded.period_starting_index != 0 {
-                println!("{:?}", expanded)
+                diagnostic!("{:?}", expanded)
             }
-            expanded
         })
         .map(|expanded| expanded.period_len())
         .filter(|len| is_odd(*len))
-        .count();
-    println!("{}", num_odd_periods);
+        .count()
 }
 
 #[derive(Debug, Eq, PartialEq)]
@@ -91,7 +108,7 @@ impl ExpandedSqrt {
 fn expanded_sqrt(n: u32) -> SqrtExpansionResult {
     let mut expansion = Vec::new();
     let mut seen = HashMap::new();
-    let a_0 = approx_sqrt(n);
+    let a_0 = isqrt(n as u64) as u32;
     let mut prev_numerator = 1;
     let mut prev_subtrahend = a_0;
     if a_0 * a_0 == n {
@@ -101,7 +118,7 @@ fn expanded_sqrt(n: u32) -> SqrtExpansionResult {
         let entry = (prev_numerator, prev_subtrahend);
         if let Some(index) = seen.remove(&entry) {
             if index != 0 {
-                println!(
+                diagnostic!(
                     "sqrt({}) = [{}; {:?}] starting @ {}",
                     n, a_0, expansion, index
                 );
@@ -124,21 +141,6 @@ fn expanded_sqrt(n: u32) -> SqrtExpansionResult {
     }
 }
 
-fn approx_sqrt(n: u32) -> u32 {
-    let mut min = 0;
-    let mut max = n;
-    let mut cur = n / 2;
-    while min < max {
-        match (cur * cur).cmp(&n) {
-            Ordering::Equal => return cur,
-            Ordering::Less => min = cur,
-            Ordering::Greater => max = cur - 1,
-        }
-        cur = (min + max + 1) / 2;
-    }
-    cur
-}
-
 fn is_odd(n: usize) -> bool {
     n % 2 != 0
 }
@@ -147,36 +149,6 @@ fn is_odd(n: usize) -> bool {
 mod test {
     use super::*;
 
-    #[test]
-    fn test_approx_sqrt() {
-        let test_cases = [
-            (0, 0),
-            (1, 1),
-            (2, 1),
-            (3, 1),
-            (4, 2),
-            (5, 2),
-            (6, 2),
-            (7, 2),
-            (8, 2),
-            (9, 3),
-            (10, 3),
-            (24, 4),
-            (25, 5),
-            (25, 5),
-            (31, 5),
-            (35, 5),
-            (36, 6),
-            (37, 6),
-            (99, 9),
-            (100, 10),
-        ];
-        for (n, expected) in test_cases {
-            let actual = approx_sqrt(n);
-            assert_eq!(expected, actual, "sqrt({})", n);
-        }
-    }
-
     #[test]
     fn test_expanded_sqrt() {
         let test_cases = [
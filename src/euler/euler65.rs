@@ -26,6 +26,8 @@ The sum of digits in the numerator of the 10th convergent is 1 + 4 + 5 + 7 = 17.
 Find the sum of digits in the numerator of the 100th convergent of the continued fraction for e.
  */
 
+use crate::problem::{Answer, Problem};
+
 use fraction::{BigUint, ToPrimitive, Zero};
 
 type Fraction = fraction::GenericFraction<BigUint>;
@@ -33,12 +35,27 @@ type Fraction = fraction::GenericFraction<BigUint>;
 const NUM_CONVERGENT: u64 = 100;
 const BASE: u64 = 10;
 
-pub fn main() {
+pub struct Euler65;
+
+impl Problem for Euler65 {
+    fn number(&self) -> usize {
+        65
+    }
+
+    fn title(&self) -> &'static str {
+        "Convergents of e"
+    }
+
+    fn solve(&self) -> Answer {
+        solve().into()
+    }
+}
+
+fn solve() -> u64 {
     let expansion = (1..NUM_CONVERGENT).map(expansion_e).rev();
     let convergent = get_convergent(2, expansion);
     let numer = convergent.numer().expect("valid fraction");
-    let sum = sum_digits(numer.clone(), BASE);
-    println!("{}", sum);
+    sum_digits(numer.clone(), BASE)
 }
 
 fn get_convergent<I>(first: u64, expansion_from_end: I) -> Fraction
@@ -17,6 +17,8 @@ as there are 299 altogether! If you could check one trillion (1012) routes every
 billion years to check them all. There is an efficient algorithm to solve it. ;o)
  */
 
+use crate::problem::{Answer, Problem};
+
 const FILE_CONTENTS: &str = include_str!("../../static/p067_triangle.txt");
 
 fn solve(triangle: &[Vec<u64>]) -> u64 {
@@ -50,8 +52,20 @@ fn parse_triangle_string(triangle_sting: &str) -> Vec<Vec<u64>> {
         .collect()
 }
 
-pub fn main() {
-    println!("{}", solve(&parse_triangle_string(&FILE_CONTENTS)));
+pub struct Euler67;
+
+impl Problem for Euler67 {
+    fn number(&self) -> usize {
+        67
+    }
+
+    fn title(&self) -> &'static str {
+        "Maximum path sum II"
+    }
+
+    fn solve(&self) -> Answer {
+        solve(&parse_triangle_string(FILE_CONTENTS)).into()
+    }
 }
 
 #[cfg(test)]
@@ -59,5 +73,5 @@ const ANSWER: u64 = 7273;
 
 #[test]
 fn test() {
-    assert_eq!(ANSWER, solve(&parse_triangle_string(&FILE_CONTENTS)));
+    assert_eq!(ANSWER, solve(&parse_triangle_string(FILE_CONTENTS)));
 }
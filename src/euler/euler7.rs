@@ -5,52 +5,44 @@ the 6th prime is 13.
 What is the 10 001st prime number?
 */
 
-fn primes_below(limit: usize) -> Vec<usize> {
-    let mut is_composite = vec![false; limit];
-    let mut primes = Vec::new();
-    for num in 2..limit {
-        if is_composite[num] {
-            continue;
-        }
-        for index in (num..).step_by(num).take_while(|&x| x < limit) {
-            is_composite[index] = true;
-        }
-        primes.push(num);
-    }
-    primes
+use crate::number_theory::prime_counting::nth_prime;
+use crate::params::{Param, Params};
+use crate::problem::{Answer, Problem};
+
+fn solve(nth: u64) -> u64 {
+    nth_prime(nth)
 }
 
-const FACTOR: usize = 10;
+const NTH: u64 = 10_001;
 
-fn estimate_nth_prime(nth: usize) -> usize {
-    nth * FACTOR
-}
+pub struct Euler7;
 
-fn nth_prime(nth: usize) -> usize {
-    let mut limit = estimate_nth_prime(nth);
-    loop {
-        let primes = primes_below(limit);
-        match primes.get(nth - 1) {
-            Some(prime) => return *prime,
-            None => {
-                limit *= FACTOR;
-            }
-        }
+impl Problem for Euler7 {
+    fn number(&self) -> usize {
+        7
     }
-}
 
-fn solve(nth: usize) -> usize {
-    nth_prime(nth)
-}
+    fn title(&self) -> &'static str {
+        "10001st prime"
+    }
+
+    fn params(&self) -> Vec<Param> {
+        vec![
+            Param::new("nth", "Which prime to find", NTH).at_least(1u64),
+        ]
+    }
 
-const NTH: usize = 10_001;
+    fn solve(&self) -> Answer {
+        solve(NTH).into()
+    }
 
-pub fn main() {
-    println!("{}", solve(NTH));
+    fn solve_with(&self, params: &Params) -> Answer {
+        solve(params.get("nth")).into()
+    }
 }
 
 #[cfg(test)]
-const ANSWER: usize = 104743;
+const ANSWER: u64 = 104743;
 
 #[test]
 fn test() {
@@ -27,6 +27,9 @@ Find the thirteen adjacent digits in the 1000-digit number that have the
 greatest product. What is the value of this product?
 */
 
+use crate::params::{Param, Params};
+use crate::problem::{Answer, Problem};
+
 const INPUT: &str = "73167176531330624919225119674426574742355349194934\
                      96983520312774506326239578318016984801869478851843\
                      85861560789112949495459501737958331952853208805511\
@@ -49,6 +52,8 @@ const INPUT: &str = "73167176531330624919225119674426574742355349194934\
                      71636269561882670428252483600823257530420752963450\
                      ";
 const NUM_DIGITS: usize = 13;
+// The product of 21 nines overflows a u64
+const MAX_NUM_DIGITS: usize = 20;
 
 fn solve(input: &str, num_digits: usize) -> u64 {
     let digits = input
@@ -63,8 +68,32 @@ fn solve(input: &str, num_digits: usize) -> u64 {
         .expect("input was too small")
 }
 
-pub fn main() {
-    println!("{}", solve(INPUT, NUM_DIGITS));
+pub struct Euler8;
+
+impl Problem for Euler8 {
+    fn number(&self) -> usize {
+        8
+    }
+
+    fn title(&self) -> &'static str {
+        "Largest product in a series"
+    }
+
+    fn params(&self) -> Vec<Param> {
+        vec![
+            Param::new("num_digits", "Number of adjacent digits to multiply", NUM_DIGITS)
+                .at_least(1usize)
+                .at_most(MAX_NUM_DIGITS),
+        ]
+    }
+
+    fn solve(&self) -> Answer {
+        solve(INPUT, NUM_DIGITS).into()
+    }
+
+    fn solve_with(&self, params: &Params) -> Answer {
+        solve(INPUT, params.get("num_digits")).into()
+    }
 }
 
 #[cfg(test)]
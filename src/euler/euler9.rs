@@ -9,6 +9,10 @@ There exists exactly one Pythagorean triplet for which a + b + c = 1000.  Find
 the product abc.
 */
 
+use crate::number_theory::divisible_by;
+use crate::params::{Param, Params};
+use crate::problem::{Answer, Problem};
+
 fn is_pythagorean_triplet(a: u64, b: u64, c: u64) -> bool {
     (a * a) + (b * b) == (c * c)
 }
@@ -17,6 +21,14 @@ fn sum_triplets(sum: u64) -> impl Iterator<Item = (u64, u64, u64)> {
     (1..sum).flat_map(move |a| (a..sum - a).map(move |b| (a, b, sum - a - b)))
 }
 
+// Every triplet is a multiple of (m² - n², 2mn, m² + n²) for some m > n > 0,
+// which sums to 2m(m + n)
+fn has_triplet(sum: u64) -> bool {
+    (2..)
+        .take_while(|&m| 2 * m * (m + 1) <= sum)
+        .any(|m| (1..m).any(|n| divisible_by(sum, 2 * m * (m + n))))
+}
+
 fn solve(sum: u64) -> u64 {
     sum_triplets(sum)
         .filter(|&(a, b, c)| is_pythagorean_triplet(a, b, c))
@@ -27,8 +39,36 @@ fn solve(sum: u64) -> u64 {
 
 const SUM: u64 = 1_000;
 
-pub fn main() {
-    println!("{}", solve(SUM));
+pub struct Euler9;
+
+impl Problem for Euler9 {
+    fn number(&self) -> usize {
+        9
+    }
+
+    fn title(&self) -> &'static str {
+        "Special Pythagorean triplet"
+    }
+
+    fn params(&self) -> Vec<Param> {
+        vec![
+            Param::new("sum", "Sum of the Pythagorean triplet", SUM).check(|&sum: &u64| {
+                if has_triplet(sum) {
+                    Ok(())
+                } else {
+                    Err(format!("no Pythagorean triplet sums to {}", sum))
+                }
+            }),
+        ]
+    }
+
+    fn solve(&self) -> Answer {
+        solve(SUM).into()
+    }
+
+    fn solve_with(&self, params: &Params) -> Answer {
+        solve(params.get("sum")).into()
+    }
 }
 
 #[cfg(test)]
@@ -38,3 +78,11 @@ const ANSWER: u64 = 31875000;
 fn test() {
     assert_eq!(ANSWER, solve(SUM));
 }
+
+#[test]
+fn sums() {
+    for sum in 1..200 {
+        let triplets = sum_triplets(sum).filter(|&(a, b, c)| is_pythagorean_triplet(a, b, c));
+        assert_eq!(triplets.count() > 0, has_triplet(sum), "{}", sum);
+    }
+}
@@ -1,38 +1,69 @@
-pub mod euler1;
-pub mod euler2;
-pub mod euler3;
-pub mod euler4;
-pub mod euler5;
-pub mod euler6;
-pub mod euler7;
-pub mod euler8;
-pub mod euler9;
-pub mod euler10;
-pub mod euler11;
-pub mod euler12;
-pub mod euler13;
-pub mod euler14;
-pub mod euler15;
-pub mod euler16;
-pub mod euler17;
-pub mod euler18;
-pub mod euler19;
-pub mod euler20;
-pub mod euler21;
-pub mod euler22;
-pub mod euler23;
-pub mod euler24;
-pub mod euler25;
-pub mod euler26;
-pub mod euler27;
-pub mod euler28;
-pub mod euler29;
-pub mod euler30;
-pub mod euler31;
-pub mod euler32;
-pub mod euler61;
-pub mod euler62;
-pub mod euler63;
-pub mod euler64;
-pub mod euler65;
-pub mod euler67;
+use crate::problem::Problem;
+
+// Declares a `pub mod eulerN` for every `eulerN.rs` in this directory along
+// with the `PROBLEMS` registry and their `STATEMENTS`. See `build.rs`.
+include!(concat!(env!("OUT_DIR"), "/problems.rs"));
+
+/// All implemented problems, ordered by problem number.
+pub fn all() -> &'static [&'static dyn Problem] {
+    PROBLEMS
+}
+
+pub fn get(number: usize) -> Option<&'static dyn Problem> {
+    PROBLEMS
+        .iter()
+        .find(|problem| problem.number() == number)
+        .copied()
+}
+
+/// The problem statement from the comment at the top of the problem's module,
+/// as written there.
+pub fn statement(number: usize) -> Option<&'static str> {
+    STATEMENTS
+        .iter()
+        .find(|&&(n, _)| n == number)
+        .map(|&(_, statement)| statement)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::params::Params;
+
+    #[test]
+    fn registry_matches_modules() {
+        let numbers = all()
+            .iter()
+            .map(|problem| problem.number())
+            .collect::<Vec<_>>();
+        assert_eq!(MODULE_NUMBERS, &numbers[..]);
+    }
+
+    #[test]
+    fn statements() {
+        for problem in all() {
+            let statement = statement(problem.number());
+            assert!(statement.is_some(), "Problem {}", problem.number());
+        }
+        assert!(statement(26)
+            .unwrap()
+            .starts_with("A unit fraction contains 1 in the numerator."));
+        assert!(statement(26).unwrap().ends_with("decimal fraction part."));
+    }
+
+    #[test]
+    fn params() {
+        for problem in all() {
+            // Checks declared with the wrong type panic here
+            let params = Params::new(problem.params(), &[]);
+            assert!(params.is_ok(), "Problem {}", problem.number());
+        }
+    }
+
+    #[test]
+    fn titles() {
+        for problem in all() {
+            assert!(!problem.title().is_empty(), "Problem {}", problem.number());
+        }
+    }
+}
@@ -0,0 +1,16 @@
+//! Project Euler solutions along with the helpers they share.
+//!
+//! The `euler-rust` binary is a command line interface on top of this crate.
+
+pub mod answers;
+pub mod bench;
+pub mod diagnostics;
+pub mod digits;
+pub mod euler;
+pub mod number_theory;
+pub mod params;
+pub mod problem;
+pub mod say;
+
+#[macro_use]
+extern crate itertools;
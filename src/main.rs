@@ -1,74 +1,567 @@
-mod digits;
-mod euler;
-mod say;
+mod pool;
+mod selection;
+mod statement;
+mod timeout;
 
-#[macro_use]
-extern crate itertools;
+use clap::{Parser, Subcommand, ValueEnum};
+use euler_rust::bench::{self, Stats};
+use euler_rust::params::{Override, Params};
+use euler_rust::problem::{Answer, Problem};
+use euler_rust::{answers, diagnostics, euler};
+use selection::Selection;
+use serde_json::json;
+use std::cmp::Reverse;
+use std::process;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-use clap::Parser;
-
-/// Run a specific problem from Project Euler
+/// Run problems from Project Euler
 #[derive(Parser)]
 #[clap(author, version, about, long_about = None)]
+#[clap(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 struct Args {
-    /// Which Project Euler problem to run
-    #[clap(value_parser)]
-    problem: usize,
+    #[clap(subcommand)]
+    command: Option<Command>,
+
+    /// Which Project Euler problems to run: a number (7), a list (3,7,10), a
+    /// range (1..=32) or "all"
+    #[clap(value_parser, required = true)]
+    problems: Option<Selection>,
+
+    /// Override a problem parameter, e.g. --param min_divisors=1000 (see the
+    /// params subcommand)
+    #[clap(short, long = "param", value_parser, value_name = "NAME=VALUE")]
+    params: Vec<Override>,
+
+    /// How many problems to run at once
+    #[clap(short, long, value_parser = parse_jobs, default_value_t = 1)]
+    jobs: usize,
+
+    /// Give up on a problem that takes longer than this, e.g. 60s or 500ms,
+    /// and report it as TIMEOUT
+    #[clap(short, long, value_parser = timeout::parse_duration)]
+    timeout: Option<Duration>,
+
+    /// Print diagnostic output from the solvers to stderr
+    #[clap(short, long, global = true)]
+    verbose: bool,
+
+    /// How to print results
+    #[clap(long, value_enum, default_value = "text", global = true)]
+    format: Format,
+}
+
+#[derive(ValueEnum, Clone, Copy, Eq, PartialEq)]
+enum Format {
+    /// A table of answers and timings, or just the answer for a single problem
+    Text,
+    /// One JSON object per problem, one per line
+    Json,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run problems and compare their answers against the known answers,
+    /// exiting with an error if any of them differ
+    Verify {
+        /// Which Project Euler problems to verify
+        #[clap(value_parser, default_value = "all")]
+        problems: Selection,
+
+        /// How many problems to run at once
+        #[clap(short, long, value_parser = parse_jobs, default_value_t = 1)]
+        jobs: usize,
+
+        /// Give up on a problem that takes longer than this, e.g. 60s or
+        /// 500ms, and report it as TIMEOUT
+        #[clap(short, long, value_parser = timeout::parse_duration)]
+        timeout: Option<Duration>,
+    },
+    /// Time repeated runs of problems, listing the slowest first
+    Bench {
+        /// Which Project Euler problems to benchmark
+        #[clap(value_parser)]
+        problems: Selection,
+
+        /// How many timed runs of each problem to make
+        #[clap(short = 'n', long, default_value_t = 10)]
+        iterations: usize,
+
+        /// How many untimed runs of each problem to make first
+        #[clap(short, long, default_value_t = 1)]
+        warmup: usize,
+    },
+    /// Print the statement of a problem
+    Show {
+        /// Which Project Euler problem to show
+        #[clap(value_parser)]
+        problem: usize,
+    },
+    /// List the implemented problems with their titles
+    List {
+        /// Which Project Euler problems to list
+        #[clap(value_parser, default_value = "all")]
+        problems: Selection,
+    },
+    /// List the parameters each problem accepts
+    Params {
+        /// Which Project Euler problems to list parameters for
+        #[clap(value_parser, default_value = "all")]
+        problems: Selection,
+    },
+}
+
+struct Run {
+    problem: &'static dyn Problem,
+    /// `None` if the problem timed out.
+    answer: Option<Answer>,
+    elapsed: Duration,
+    overridden: bool,
+}
+
+/// How to run the selected problems.
+#[derive(Clone, Copy)]
+struct RunOptions {
+    jobs: usize,
+    timeout: Option<Duration>,
+}
+
+impl Run {
+    fn expected(&self) -> Option<&'static str> {
+        answers::known_answer(self.problem.number())
+    }
+
+    /// Whether the answer matches the known answer, or `None` if there is no
+    /// known answer (including when parameters were overridden).
+    fn verified(&self) -> Option<bool> {
+        if self.overridden {
+            return None;
+        }
+        self.expected().map(|expected| match &self.answer {
+            Some(answer) => expected == answer.to_string(),
+            None => false,
+        })
+    }
+
+    fn is_verified(&self) -> bool {
+        self.verified() == Some(true)
+    }
+
+    fn timed_out(&self) -> bool {
+        self.answer.is_none()
+    }
+
+    fn answer_string(&self) -> String {
+        match &self.answer {
+            Some(answer) => answer.to_string(),
+            None => String::from(TIMEOUT),
+        }
+    }
 }
 
 fn main() {
     let args = Args::parse();
-    let problem = args.problem;
-    let main = match get_main(problem) {
+    diagnostics::set_enabled(args.verbose);
+    match args.command {
         None => {
-            eprintln!("No implementation found for problem {}", problem);
-            return;
+            let selection = args.problems.expect("problems are required");
+            let options = RunOptions {
+                jobs: args.jobs,
+                timeout: args.timeout,
+            };
+            run_problems(&selection, &args.params, options, args.format);
         }
-        Some(main) => main,
+        Some(Command::Verify {
+            problems,
+            jobs,
+            timeout,
+        }) => verify_problems(&problems, RunOptions { jobs, timeout }, args.format),
+        Some(Command::Bench {
+            problems,
+            iterations,
+            warmup,
+        }) => bench_problems(&problems, iterations, warmup, args.format),
+        Some(Command::Show { problem }) => show_problem(problem, args.format),
+        Some(Command::List { problems }) => list_problems(&problems, args.format),
+        Some(Command::Params { problems }) => print_params(&problems),
+    }
+}
+
+fn parse_jobs(s: &str) -> Result<usize, String> {
+    match s.parse() {
+        Ok(0) => Err(String::from("At least one job is required")),
+        Ok(jobs) => Ok(jobs),
+        Err(_) => Err(format!("Invalid number of jobs \"{}\"", s)),
+    }
+}
+
+fn run_problems(
+    selection: &Selection,
+    overrides: &[Override],
+    options: RunOptions,
+    format: Format,
+) {
+    let runs = run_selection(selection, overrides, options);
+    match format {
+        Format::Text if selection.is_single() => {
+            for run in runs.iter() {
+                println!("{}", run.answer_string());
+            }
+        }
+        Format::Text => print_table(&runs, false),
+        Format::Json => print_json(&runs),
+    }
+    let timeouts = runs.iter().filter(|run| run.timed_out()).count();
+    if timeouts > 0 {
+        eprintln!("{} of {} problems timed out", timeouts, runs.len());
+    }
+    // Problems that were asked for but don't exist were warned about already
+    if timeouts > 0 || !missing_problems(selection).is_empty() {
+        process::exit(1);
+    }
+}
+
+fn verify_problems(selection: &Selection, options: RunOptions, format: Format) {
+    let runs = run_selection(selection, &[], options);
+    match format {
+        Format::Text => print_table(&runs, true),
+        Format::Json => print_json(&runs),
+    }
+    let failures = runs
+        .iter()
+        .filter(|run| !run.is_verified())
+        .collect::<Vec<_>>();
+    // A problem that was asked for but doesn't exist can't be verified
+    let missing = missing_problems(selection);
+    if failures.is_empty() && missing.is_empty() {
+        return;
+    }
+    if !failures.is_empty() {
+        eprintln!();
+        for run in failures.iter() {
+            eprintln!(
+                "Problem {} ({}):",
+                run.problem.number(),
+                run.problem.title()
+            );
+            eprintln!("  expected: {}", run.expected().unwrap_or("<unknown>"));
+            eprintln!("  actual:   {}", run.answer_string());
+        }
+        eprintln!();
+        eprintln!("{} of {} problems failed", failures.len(), runs.len());
+    }
+    if !missing.is_empty() {
+        eprintln!(
+            "Could not verify problems without an implementation: {}",
+            missing
+                .iter()
+                .map(|problem| problem.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    }
+    process::exit(1);
+}
+
+/// Runs the selected problems, returning the runs in problem order.
+fn run_selection(selection: &Selection, overrides: &[Override], options: RunOptions) -> Vec<Run> {
+    warn_missing(selection);
+    let problems = selected_problems(selection);
+    for o in overrides {
+        let declared = problems
+            .iter()
+            .any(|problem| problem.params().iter().any(|param| param.name() == o.name));
+        if !declared {
+            exit_with_error(&format!(
+                "No selected problem has a parameter named {} (see the params subcommand)",
+                o.name
+            ));
+        }
+    }
+    let work = problems
+        .into_iter()
+        .map(|problem| {
+            let declared = problem.params();
+            let overridden = overrides
+                .iter()
+                .any(|o| declared.iter().any(|param| param.name() == o.name));
+            if !overridden {
+                return (problem, None);
+            }
+            let params = Params::new(declared, overrides).unwrap_or_else(|message| {
+                exit_with_error(&format!("Problem {}: {}", problem.number(), message))
+            });
+            (problem, Some(Arc::new(params)))
+        })
+        .collect::<Vec<_>>();
+    pool::map_ordered(&work, options.jobs, |(problem, params)| {
+        run(*problem, params.clone(), options.timeout)
+    })
+}
+
+fn warn_missing(selection: &Selection) {
+    for problem in missing_problems(selection) {
+        eprintln!("No implementation found for problem {}", problem);
+    }
+}
+
+/// The explicitly selected problems that have no implementation.
+fn missing_problems(selection: &Selection) -> Vec<usize> {
+    selection
+        .explicit()
+        .filter(|&problem| euler::get(problem).is_none())
+        .collect()
+}
+
+fn selected_problems(selection: &Selection) -> Vec<&'static dyn Problem> {
+    euler::all()
+        .iter()
+        .filter(|problem| selection.contains(problem.number()))
+        .copied()
+        .collect()
+}
+
+/// Solves `problem` as stated, or with `params` if any were overridden, giving
+/// up after `timeout` if there is one.
+fn run(
+    problem: &'static dyn Problem,
+    params: Option<Arc<Params>>,
+    timeout: Option<Duration>,
+) -> Run {
+    let overridden = params.is_some();
+    let solve = move || {
+        let start = Instant::now();
+        let answer = match params {
+            None => problem.solve(),
+            Some(params) => problem.solve_with(&params),
+        };
+        (answer, start.elapsed())
+    };
+    let (answer, elapsed) = match timeout {
+        None => {
+            let (answer, elapsed) = solve();
+            (Some(answer), elapsed)
+        }
+        Some(timeout) => match timeout::with_timeout(timeout, solve) {
+            Some((answer, elapsed)) => (Some(answer), elapsed),
+            None => (None, timeout),
+        },
     };
-    main();
-}
-
-fn get_main(problem: usize) -> Option<Box<dyn Fn()>> {
-    Some(Box::new(match problem {
-        1 => euler::euler1::main,
-        2 => euler::euler2::main,
-        3 => euler::euler3::main,
-        4 => euler::euler4::main,
-        5 => euler::euler5::main,
-        6 => euler::euler6::main,
-        7 => euler::euler7::main,
-        8 => euler::euler8::main,
-        9 => euler::euler9::main,
-        10 => euler::euler10::main,
-        11 => euler::euler11::main,
-        12 => euler::euler12::main,
-        13 => euler::euler13::main,
-        14 => euler::euler14::main,
-        15 => euler::euler15::main,
-        16 => euler::euler16::main,
-        17 => euler::euler17::main,
-        18 => euler::euler18::main,
-        19 => euler::euler19::main,
-        20 => euler::euler20::main,
-        21 => euler::euler21::main,
-        22 => euler::euler22::main,
-        23 => euler::euler23::main,
-        24 => euler::euler24::main,
-        25 => euler::euler25::main,
-        26 => euler::euler26::main,
-        27 => euler::euler27::main,
-        28 => euler::euler28::main,
-        29 => euler::euler29::main,
-        30 => euler::euler30::main,
-        31 => euler::euler31::main,
-        32 => euler::euler32::main,
-        61 => euler::euler61::main,
-        62 => euler::euler62::main,
-        63 => euler::euler63::main,
-        64 => euler::euler64::main,
-        65 => euler::euler65::main,
-        67 => euler::euler67::main,
-        _ => return None,
-    }))
+    Run {
+        problem,
+        answer,
+        elapsed,
+        overridden,
+    }
+}
+
+fn exit_with_error(message: &str) -> ! {
+    eprintln!("error: {}", message);
+    process::exit(2);
+}
+
+fn bench_problems(selection: &Selection, iterations: usize, warmup: usize, format: Format) {
+    if iterations == 0 {
+        exit_with_error("At least one iteration is required");
+    }
+    warn_missing(selection);
+    let mut results = selected_problems(selection)
+        .into_iter()
+        .map(|problem| {
+            let stats = bench::bench(problem, warmup, iterations).expect("no iterations run");
+            (problem, stats)
+        })
+        .collect::<Vec<_>>();
+    results.sort_by_key(|(_, stats)| Reverse(stats.median));
+    match format {
+        Format::Text => print_bench_table(&results),
+        Format::Json => print_bench_json(&results),
+    }
+}
+
+fn show_problem(number: usize, format: Format) {
+    let problem = euler::get(number).unwrap_or_else(|| {
+        exit_with_error(&format!("No implementation found for problem {}", number))
+    });
+    let statement = euler::statement(number).unwrap_or_default();
+    match format {
+        Format::Text => {
+            println!("Problem {}: {}", number, problem.title());
+            println!();
+            println!("{}", statement::rewrap(statement, statement::WRAP_WIDTH));
+        }
+        Format::Json => {
+            let object = json!({
+                "problem": number,
+                "title": problem.title(),
+                "statement": statement,
+            });
+            println!("{}", object);
+        }
+    }
+}
+
+fn list_problems(selection: &Selection, format: Format) {
+    warn_missing(selection);
+    let problems = selected_problems(selection);
+    match format {
+        Format::Text => {
+            let mut rows = vec![["Problem", "Title"].map(String::from).to_vec()];
+            rows.extend(
+                problems
+                    .iter()
+                    .map(|problem| vec![problem.number().to_string(), problem.title().to_string()]),
+            );
+            print_rows(&rows, &[true, false]);
+        }
+        Format::Json => {
+            for problem in problems {
+                let object = json!({
+                    "problem": problem.number(),
+                    "title": problem.title(),
+                });
+                println!("{}", object);
+            }
+        }
+    }
+}
+
+fn print_params(selection: &Selection) {
+    let mut rows = vec![["Problem", "Parameter", "Type", "Default", "Description"]
+        .map(String::from)
+        .to_vec()];
+    for problem in selected_problems(selection) {
+        rows.extend(problem.params().iter().map(|param| {
+            vec![
+                problem.number().to_string(),
+                param.name().to_string(),
+                param.type_name().to_string(),
+                param.default_string().to_string(),
+                param.description().to_string(),
+            ]
+        }));
+    }
+    print_rows(&rows, &[true, false, false, false, false]);
+}
+
+const OK: &str = "ok";
+const WRONG: &str = "WRONG";
+const UNKNOWN: &str = "UNKNOWN";
+const TIMEOUT: &str = "TIMEOUT";
+
+fn print_table(runs: &[Run], verify: bool) {
+    let mut headers = vec!["Problem", "Title", "Answer", "Time"];
+    let mut right_aligned = vec![true, false, false, true];
+    if verify {
+        headers.push("Status");
+        right_aligned.push(false);
+    }
+    let mut rows = vec![headers.into_iter().map(String::from).collect::<Vec<_>>()];
+    rows.extend(runs.iter().map(|run| {
+        let mut row = vec![
+            run.problem.number().to_string(),
+            run.problem.title().to_string(),
+            run.answer_string(),
+            format!("{:.3?}", run.elapsed),
+        ];
+        if verify {
+            let status = match run.expected() {
+                _ if run.timed_out() => TIMEOUT,
+                None => UNKNOWN,
+                Some(_) if run.is_verified() => OK,
+                Some(_) => WRONG,
+            };
+            row.push(String::from(status));
+        }
+        row
+    }));
+    let total: Duration = runs.iter().map(|run| run.elapsed).sum();
+    let mut total_row = vec![
+        String::from("Total"),
+        String::new(),
+        String::new(),
+        format!("{:.3?}", total),
+    ];
+    total_row.resize(right_aligned.len(), String::new());
+    rows.push(total_row);
+    print_rows(&rows, &right_aligned);
+}
+
+fn print_bench_table(results: &[(&'static dyn Problem, Stats)]) {
+    let mut rows = vec![[
+        "Rank", "Problem", "Title", "Min", "Median", "Mean", "Std dev",
+    ]
+    .map(String::from)
+    .to_vec()];
+    rows.extend(results.iter().enumerate().map(|(i, (problem, stats))| {
+        vec![
+            (i + 1).to_string(),
+            problem.number().to_string(),
+            problem.title().to_string(),
+            format!("{:.3?}", stats.min),
+            format!("{:.3?}", stats.median),
+            format!("{:.3?}", stats.mean),
+            format!("{:.3?}", stats.stddev),
+        ]
+    }));
+    print_rows(&rows, &[true, true, false, true, true, true, true]);
+}
+
+fn print_rows(rows: &[Vec<String>], right_aligned: &[bool]) {
+    let widths = (0..right_aligned.len())
+        .map(|col| {
+            rows.iter()
+                .map(|row| row[col].chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect::<Vec<_>>();
+    for row in rows {
+        let cells = row
+            .iter()
+            .zip(widths.iter())
+            .zip(right_aligned)
+            .map(|((cell, &width), &right_aligned)| {
+                if right_aligned {
+                    format!("{:>width$}", cell)
+                } else {
+                    format!("{:<width$}", cell)
+                }
+            })
+            .collect::<Vec<_>>();
+        println!("{}", cells.join("  ").trim_end());
+    }
+}
+
+fn print_json(runs: &[Run]) {
+    for run in runs {
+        let object = json!({
+            "problem": run.problem.number(),
+            "title": run.problem.title(),
+            "answer": run.answer.as_ref().map(Answer::to_string),
+            "elapsed_ms": millis(run.elapsed),
+            "timed_out": run.timed_out(),
+            "verified": run.verified(),
+        });
+        println!("{}", object);
+    }
+}
+
+fn print_bench_json(results: &[(&'static dyn Problem, Stats)]) {
+    for (problem, stats) in results {
+        let object = json!({
+            "problem": problem.number(),
+            "title": problem.title(),
+            "iterations": stats.iterations,
+            "min_ms": millis(stats.min),
+            "median_ms": millis(stats.median),
+            "mean_ms": millis(stats.mean),
+            "stddev_ms": millis(stats.stddev),
+        });
+        println!("{}", object);
+    }
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_nanos() as f64 / 1_000_000.0
 }
@@ -0,0 +1,521 @@
+//! Prime factorization by trial division and Pollard–Brent rho, with
+//! Miller–Rabin to tell when a factor is prime.
+
+use crate::number_theory::divisible_by;
+use crate::number_theory::modular::{add_mod_u128, mul_mod_u128};
+use crate::number_theory::primality::is_prime_u128;
+use num_traits::{PrimInt, Unsigned};
+use std::fmt;
+use std::iter;
+
+/// Factors below this are found by trial division, before trying rho.
+const TRIAL_DIVISION_LIMIT: u128 = 1_000;
+
+/// How many steps of rho to take between gcds.
+const BATCH_SIZE: u128 = 128;
+
+/// A number as a product of prime powers, with the primes in increasing order.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Factorization<T> {
+    prime_powers: Vec<(T, u32)>,
+}
+
+impl<T: PrimInt + Unsigned> Factorization<T> {
+    /// The factorization of 1, with no primes.
+    pub fn one() -> Self {
+        Factorization {
+            prime_powers: Vec::new(),
+        }
+    }
+
+    /// The primes and their exponents, in increasing order of prime.
+    pub fn prime_powers(&self) -> &[(T, u32)] {
+        &self.prime_powers
+    }
+
+    /// The distinct primes, in increasing order.
+    pub fn primes(&self) -> impl DoubleEndedIterator<Item = T> + '_ {
+        self.prime_powers.iter().map(|&(p, _)| p)
+    }
+
+    /// The exponent of `p`, which is 0 if `p` isn't a factor.
+    pub fn exponent(&self, p: T) -> u32 {
+        self.prime_powers
+            .binary_search_by(|&(q, _)| q.cmp(&p))
+            .map_or(0, |i| self.prime_powers[i].1)
+    }
+
+    /// The number that this is the factorization of. Panics on overflow.
+    pub fn value(&self) -> T {
+        self.prime_powers.iter().fold(T::one(), |product, &(p, e)| {
+            (0..e)
+                .try_fold(product, |product, _| product.checked_mul(&p))
+                .expect("factorization overflows")
+        })
+    }
+
+    pub fn is_prime(&self) -> bool {
+        matches!(self.prime_powers[..], [(_, 1)])
+    }
+
+    /// τ(n), the number of divisors.
+    pub fn tau(&self) -> T {
+        self.prime_powers
+            .iter()
+            .map(|&(_, e)| T::from(e + 1).expect("exponents fit"))
+            .fold(T::one(), |product, count| product * count)
+    }
+
+    /// σ_k(n), the sum of the `k`th powers of the divisors. σ_0 is τ and σ_1
+    /// is the sum of the divisors. Panics on overflow.
+    pub fn sigma(&self, k: u32) -> T {
+        // σ_k is multiplicative, and σ_k(p^e) = 1 + p^k + p^2k + ... + p^ek
+        self.prime_powers
+            .iter()
+            .map(|&(p, e)| {
+                let p_k = checked_pow(p, k);
+                (0..e)
+                    .try_fold((T::one(), T::one()), |(sum, power), _| {
+                        let power = power.checked_mul(&p_k)?;
+                        Some((sum.checked_add(&power)?, power))
+                    })
+                    .map(|(sum, _)| sum)
+            })
+            .try_fold(T::one(), |product, sum| product.checked_mul(&sum?))
+            .expect("sigma overflows")
+    }
+
+    /// φ(n), how many numbers up to n are coprime to it.
+    pub fn phi(&self) -> T {
+        self.prime_powers.iter().fold(T::one(), |product, &(p, e)| {
+            product * checked_pow(p, e - 1) * (p - T::one())
+        })
+    }
+
+    /// λ(n), the Carmichael function: the smallest m such that a^m ≡ 1
+    /// (mod n) for every a coprime to n. It divides φ(n), and is the order of
+    /// a primitive root when n has one.
+    pub fn carmichael(&self) -> T {
+        let two = T::one() + T::one();
+        self.prime_powers.iter().fold(T::one(), |lcm, &(p, e)| {
+            // The units modulo 2^e aren't cyclic for e ≥ 3, and have
+            // exponent 2^(e - 2)
+            let lambda = if p == two && e >= 3 {
+                checked_pow(p, e - 2)
+            } else {
+                checked_pow(p, e - 1) * (p - T::one())
+            };
+            lcm / gcd(lcm, lambda) * lambda
+        })
+    }
+
+    /// All the divisors, in increasing order.
+    pub fn divisors(&self) -> Vec<T> {
+        let mut divisors = vec![T::one()];
+        for &(p, e) in self.prime_powers.iter() {
+            let previous = divisors.len();
+            let mut power = T::one();
+            for _ in 0..e {
+                power = power * p;
+                for i in 0..previous {
+                    divisors.push(divisors[i] * power);
+                }
+            }
+        }
+        divisors.sort_unstable();
+        divisors
+    }
+
+    /// The product of the distinct primes.
+    pub fn radical(&self) -> T {
+        self.primes().fold(T::one(), |product, p| product * p)
+    }
+
+    /// The factorization of the greatest common divisor, taking the smaller
+    /// exponent of each prime.
+    pub fn gcd(&self, other: &Self) -> Self {
+        let prime_powers = merge(&self.prime_powers, &other.prime_powers)
+            .filter_map(|(p, a, b)| match a.min(b) {
+                0 => None,
+                e => Some((p, e)),
+            })
+            .collect();
+        Factorization { prime_powers }
+    }
+
+    /// The factorization of the least common multiple, taking the larger
+    /// exponent of each prime.
+    pub fn lcm(&self, other: &Self) -> Self {
+        let prime_powers = merge(&self.prime_powers, &other.prime_powers)
+            .map(|(p, a, b)| (p, a.max(b)))
+            .collect();
+        Factorization { prime_powers }
+    }
+}
+
+fn checked_pow<T: PrimInt>(base: T, exponent: u32) -> T {
+    (0..exponent)
+        .try_fold(T::one(), |power, _| power.checked_mul(&base))
+        .expect("power overflows")
+}
+
+/// Walks two lists of prime powers in order of prime, yielding each prime in
+/// either along with its exponent in both (0 if missing).
+fn merge<'a, T: PrimInt>(
+    a: &'a [(T, u32)],
+    b: &'a [(T, u32)],
+) -> impl Iterator<Item = (T, u32, u32)> + 'a {
+    let (mut a, mut b) = (a.iter().peekable(), b.iter().peekable());
+    iter::from_fn(move || match (a.peek(), b.peek()) {
+        (Some(&&(p, e)), Some(&&(q, _))) if p < q => {
+            a.next();
+            Some((p, e, 0))
+        }
+        (Some(&&(p, e)), Some(&&(q, f))) if p == q => {
+            a.next();
+            b.next();
+            Some((p, e, f))
+        }
+        (_, Some(&&(q, f))) => {
+            b.next();
+            Some((q, 0, f))
+        }
+        (Some(&&(p, e)), None) => {
+            a.next();
+            Some((p, e, 0))
+        }
+        (None, None) => None,
+    })
+}
+
+impl<T: PrimInt + Unsigned> FromIterator<T> for Factorization<T> {
+    /// Collects primes, repeated according to their multiplicity.
+    fn from_iter<I: IntoIterator<Item = T>>(primes: I) -> Self {
+        let mut primes = primes.into_iter().collect::<Vec<_>>();
+        primes.sort_unstable();
+        let mut prime_powers: Vec<(T, u32)> = Vec::new();
+        for p in primes {
+            match prime_powers.last_mut() {
+                Some((q, e)) if *q == p => *e += 1,
+                _ => prime_powers.push((p, 1)),
+            }
+        }
+        Factorization { prime_powers }
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Factorization<T> {
+    /// Formats as e.g. `2^3 * 3 * 5`, or `1` if there are no primes.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.prime_powers.is_empty() {
+            return write!(f, "1");
+        }
+        for (i, (p, e)) in self.prime_powers.iter().enumerate() {
+            if i > 0 {
+                write!(f, " * ")?;
+            }
+            match e {
+                1 => write!(f, "{}", p)?,
+                _ => write!(f, "{}^{}", p, e)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The prime factorization of `n`. Panics if `n` is 0.
+pub fn factorize<T: PrimInt + Unsigned>(n: T) -> Factorization<T> {
+    let n = n.to_u128().expect("unsigned integers fit in u128");
+    assert!(n > 0, "0 has no factorization");
+    let mut primes = Vec::new();
+    let n = trial_divide(n, &mut primes);
+    factor_into(n, &mut primes);
+    primes
+        .into_iter()
+        .map(|p| T::from(p).expect("factors are no larger than n"))
+        .collect()
+}
+
+/// Divides out the factors below the trial division limit, adding them to
+/// `primes` and returning what's left.
+fn trial_divide(mut n: u128, primes: &mut Vec<u128>) -> u128 {
+    let candidates = [2, 3]
+        .into_iter()
+        .chain((5..).step_by(6).flat_map(|k| [k, k + 2]));
+    for d in candidates.take_while(|&d| d < TRIAL_DIVISION_LIMIT) {
+        if d * d > n {
+            break;
+        }
+        while divisible_by(n, d) {
+            primes.push(d);
+            n /= d;
+        }
+    }
+    // Whatever is left is prime if it has no factor below its square root
+    if n > 1 && n < TRIAL_DIVISION_LIMIT * TRIAL_DIVISION_LIMIT {
+        primes.push(n);
+        return 1;
+    }
+    n
+}
+
+fn factor_into(n: u128, primes: &mut Vec<u128>) {
+    if n == 1 {
+        return;
+    }
+    if is_prime_u128(n) {
+        primes.push(n);
+        return;
+    }
+    let d = (1..)
+        .find_map(|c| pollard_brent(n, c))
+        .expect("composites have a nontrivial factor");
+    factor_into(d, primes);
+    factor_into(n / d, primes);
+}
+
+/// Looks for a nontrivial factor of composite `n` with Brent's variant of
+/// Pollard's rho, iterating `x -> x^2 + c`. Returns `None` if this `c` fails.
+fn pollard_brent(n: u128, c: u128) -> Option<u128> {
+    let f = |x: u128| add_mod_u128(mul_mod_u128(x, x, n), c % n, n);
+    let (mut x, mut y, mut ys) = (2, 2, 2);
+    let (mut r, mut q, mut g) = (1, 1, 1);
+    while g == 1 {
+        x = y;
+        for _ in 0..r {
+            y = f(y);
+        }
+        let mut k = 0;
+        while k < r && g == 1 {
+            ys = y;
+            for _ in 0..BATCH_SIZE.min(r - k) {
+                y = f(y);
+                q = mul_mod_u128(q, x.abs_diff(y), n);
+            }
+            g = gcd(q, n);
+            k += BATCH_SIZE;
+        }
+        r *= 2;
+    }
+    if g == n {
+        // The batch overshot, so retrace it one step at a time
+        loop {
+            ys = f(ys);
+            g = gcd(x.abs_diff(ys), n);
+            if g > 1 {
+                break;
+            }
+        }
+    }
+    (g != n).then_some(g)
+}
+
+fn gcd<T: PrimInt>(mut a: T, mut b: T) -> T {
+    while !b.is_zero() {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::number_theory::modular::pow_mod;
+
+    fn factorize_naive(mut n: u64) -> Vec<(u64, u32)> {
+        let mut prime_powers = Vec::new();
+        let mut d = 2;
+        while d * d <= n {
+            let mut e = 0;
+            while divisible_by(n, d) {
+                n /= d;
+                e += 1;
+            }
+            if e > 0 {
+                prime_powers.push((d, e));
+            }
+            d += 1;
+        }
+        if n > 1 {
+            prime_powers.push((n, 1));
+        }
+        prime_powers
+    }
+
+    #[test]
+    fn small() {
+        for n in 1..5_000u64 {
+            let factorization = factorize(n);
+            assert_eq!(factorize_naive(n), factorization.prime_powers(), "{}", n);
+            assert_eq!(n, factorization.value());
+        }
+    }
+
+    #[test]
+    fn large() {
+        let cases: [(u64, &[(u64, u32)]); 5] = [
+            (
+                600_851_475_143,
+                &[(71, 1), (839, 1), (1_471, 1), (6_857, 1)],
+            ),
+            (
+                1_000_000_007 * 998_244_353,
+                &[(998_244_353, 1), (1_000_000_007, 1)],
+            ),
+            (
+                4_294_967_291 * 4_294_967_279,
+                &[(4_294_967_279, 1), (4_294_967_291, 1)],
+            ),
+            (1 << 63, &[(2, 63)]),
+            (
+                18_446_744_073_709_551_557,
+                &[(18_446_744_073_709_551_557, 1)],
+            ),
+        ];
+        for (n, expected) in cases {
+            assert_eq!(expected, factorize(n).prime_powers(), "{}", n);
+        }
+    }
+
+    #[test]
+    fn u128() {
+        // 2^128 - 1 = 3 * 5 * 17 * 257 * 641 * 65537 * 274177 * 6700417 *
+        // 67280421310721
+        let factorization = factorize(u128::MAX);
+        let primes = factorization.primes().collect::<Vec<_>>();
+        let expected = [
+            3,
+            5,
+            17,
+            257,
+            641,
+            65_537,
+            274_177,
+            6_700_417,
+            67_280_421_310_721,
+        ];
+        assert_eq!(&expected[..], &primes[..]);
+        assert_eq!(u128::MAX, factorization.value());
+
+        // A 20 digit semiprime
+        let factorization = factorize(99_999_999_100_000_001_881u128);
+        assert_eq!(
+            &[(9_999_999_943, 1), (9_999_999_967, 1)],
+            factorization.prime_powers()
+        );
+
+        // A strong pseudoprime to the first twelve primes
+        let factorization = factorize(318_665_857_834_031_151_167_461u128);
+        assert_eq!(
+            &[(399_165_290_221, 1), (798_330_580_441, 1)],
+            factorization.prime_powers()
+        );
+
+        let n = 18_446_744_073_709_551_557u128 * 4_294_967_291;
+        let factorization = factorize(n);
+        assert_eq!(
+            &[(4_294_967_291, 1), (18_446_744_073_709_551_557, 1)],
+            factorization.prime_powers()
+        );
+    }
+
+    #[test]
+    fn accessors() {
+        let factorization = factorize(360u32);
+        assert_eq!(&[(2, 3), (3, 2), (5, 1)], factorization.prime_powers());
+        assert_eq!(3, factorization.exponent(2));
+        assert_eq!(0, factorization.exponent(7));
+        assert_eq!("2^3 * 3^2 * 5", factorization.to_string());
+        assert_eq!("1", factorize(1u32).to_string());
+        assert!(factorize(13u8).is_prime());
+        assert!(!factorize(9u8).is_prime());
+        assert!(!factorize(1u8).is_prime());
+        assert_eq!(Factorization::one(), factorize(1usize));
+        assert_eq!(factorization, [5, 2, 3, 2, 3, 2].into_iter().collect());
+    }
+
+    fn divisors_naive(n: u64) -> Vec<u64> {
+        (1..=n).filter(|&d| divisible_by(n, d)).collect()
+    }
+
+    #[test]
+    fn divisor_functions() {
+        for n in 1..1_000u64 {
+            let factorization = factorize(n);
+            let divisors = divisors_naive(n);
+            assert_eq!(divisors, factorization.divisors(), "{}", n);
+            assert_eq!(divisors.len() as u64, factorization.tau(), "{}", n);
+            for k in 0..3 {
+                let sigma = divisors.iter().map(|d| d.pow(k)).sum::<u64>();
+                assert_eq!(sigma, factorization.sigma(k), "{} {}", n, k);
+            }
+            let radical = factorization.primes().product::<u64>();
+            assert_eq!(radical, factorization.radical(), "{}", n);
+        }
+        assert_eq!(1, Factorization::<u32>::one().tau());
+        assert_eq!(vec![1], Factorization::<u32>::one().divisors());
+        assert_eq!(1, Factorization::<u32>::one().radical());
+    }
+
+    #[test]
+    fn totients() {
+        for n in 1..300u64 {
+            let factorization = factorize(n);
+            let units = (1..=n).filter(|&a| gcd(a, n) == 1).collect::<Vec<_>>();
+            assert_eq!(units.len() as u64, factorization.phi(), "φ({})", n);
+            let lambda = (1..)
+                .find(|&m| units.iter().all(|&a| pow_mod(a, m, n) == 1 % n))
+                .unwrap();
+            assert_eq!(lambda, factorization.carmichael(), "λ({})", n);
+        }
+        assert_eq!(1, Factorization::<u32>::one().phi());
+        assert_eq!(1, Factorization::<u32>::one().carmichael());
+        assert_eq!(2u64.pow(61), factorize(2u64.pow(63)).carmichael());
+    }
+
+    #[test]
+    fn known_values() {
+        // 28 is perfect, 220 and 284 are amicable
+        assert_eq!(2 * 28, factorize(28u32).sigma(1));
+        assert_eq!(220 + 284, factorize(220u32).sigma(1));
+        assert_eq!(220 + 284, factorize(284u32).sigma(1));
+        assert_eq!(576, factorize(76_576_500u64).tau());
+        assert_eq!(30, factorize(360u32).radical());
+    }
+
+    #[test]
+    #[should_panic]
+    fn sigma_overflow() {
+        factorize(u8::MAX - 3).sigma(1);
+    }
+
+    #[test]
+    fn gcd_lcm() {
+        for a in 1..100u64 {
+            for b in 1..100u64 {
+                let (fa, fb) = (factorize(a), factorize(b));
+                let gcd = (1..=a.min(b))
+                    .rev()
+                    .find(|&d| divisible_by(a, d) && divisible_by(b, d));
+                assert_eq!(gcd, Some(fa.gcd(&fb).value()), "gcd({}, {})", a, b);
+                assert_eq!(
+                    a * b / gcd.unwrap(),
+                    fa.lcm(&fb).value(),
+                    "lcm({}, {})",
+                    a,
+                    b
+                );
+            }
+        }
+        let lcm = (1..=20u64)
+            .map(factorize)
+            .fold(Factorization::one(), |lcm, f| lcm.lcm(&f));
+        assert_eq!(232_792_560, lcm.value());
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero() {
+        factorize(0u64);
+    }
+}
@@ -0,0 +1,196 @@
+//! A linear sieve tabulating multiplicative functions for every n up to a
+//! limit.
+
+use crate::number_theory::factorization::Factorization;
+
+/// Euler's totient φ, the Möbius function μ, the divisor count τ, the divisor
+/// sum σ and the smallest prime factor of every number up to and including a
+/// limit.
+///
+/// Each composite is crossed off exactly once, by its smallest prime factor,
+/// so the whole table takes linear time. Values are stored compactly (about 21
+/// bytes per number), so the limit can't exceed `u32::MAX`.
+#[derive(Debug, Clone)]
+pub struct LinearSieve {
+    primes: Vec<u32>,
+    smallest_prime_factor: Vec<u32>,
+    phi: Vec<u32>,
+    mu: Vec<i8>,
+    tau: Vec<u32>,
+    sigma: Vec<u64>,
+}
+
+impl LinearSieve {
+    pub fn new(limit: usize) -> Self {
+        assert!(limit <= u32::MAX as usize, "limit {} is too large", limit);
+        let size = limit + 1;
+        let mut sieve = LinearSieve {
+            primes: Vec::new(),
+            smallest_prime_factor: vec![0; size],
+            phi: vec![0; size],
+            mu: vec![0; size],
+            tau: vec![0; size],
+            sigma: vec![0; size],
+        };
+        // For each n, the exponent e of its smallest prime factor p and
+        // 1 + p + ... + p^e, which are needed to update τ and σ when n is
+        // multiplied by p again.
+        let mut exponent = vec![0u8; size];
+        let mut power_sum = vec![0u64; size];
+        if limit >= 1 {
+            sieve.phi[1] = 1;
+            sieve.mu[1] = 1;
+            sieve.tau[1] = 1;
+            sieve.sigma[1] = 1;
+        }
+        for n in 2..size {
+            if sieve.smallest_prime_factor[n] == 0 {
+                let p = n as u32;
+                sieve.primes.push(p);
+                sieve.smallest_prime_factor[n] = p;
+                sieve.phi[n] = p - 1;
+                sieve.mu[n] = -1;
+                sieve.tau[n] = 2;
+                sieve.sigma[n] = p as u64 + 1;
+                exponent[n] = 1;
+                power_sum[n] = p as u64 + 1;
+            }
+            let spf = sieve.smallest_prime_factor[n];
+            for i in 0..sieve.primes.len() {
+                let p = sieve.primes[i];
+                let m = n * p as usize;
+                if p > spf || m >= size {
+                    break;
+                }
+                sieve.smallest_prime_factor[m] = p;
+                if p == spf {
+                    // p already divides n, so only p's part of each function
+                    // changes
+                    let e = exponent[n] as u32;
+                    sieve.phi[m] = sieve.phi[n] * p;
+                    sieve.mu[m] = 0;
+                    sieve.tau[m] = sieve.tau[n] / (e + 1) * (e + 2);
+                    exponent[m] = exponent[n] + 1;
+                    power_sum[m] = power_sum[n] * p as u64 + 1;
+                    sieve.sigma[m] = sieve.sigma[n] / power_sum[n] * power_sum[m];
+                } else {
+                    // p is coprime to n
+                    sieve.phi[m] = sieve.phi[n] * (p - 1);
+                    sieve.mu[m] = -sieve.mu[n];
+                    sieve.tau[m] = sieve.tau[n] * 2;
+                    exponent[m] = 1;
+                    power_sum[m] = p as u64 + 1;
+                    sieve.sigma[m] = sieve.sigma[n] * (p as u64 + 1);
+                }
+            }
+        }
+        sieve
+    }
+
+    pub fn limit(&self) -> usize {
+        self.smallest_prime_factor.len() - 1
+    }
+
+    /// The primes up to the limit, in increasing order.
+    pub fn primes(&self) -> &[u32] {
+        &self.primes
+    }
+
+    pub fn is_prime(&self, n: usize) -> bool {
+        n >= 2 && self.smallest_prime_factor[n] as usize == n
+    }
+
+    /// The smallest prime factor of `n`, or `None` for 0 and 1.
+    pub fn smallest_prime_factor(&self, n: usize) -> Option<usize> {
+        match self.smallest_prime_factor[n] {
+            0 => None,
+            p => Some(p as usize),
+        }
+    }
+
+    /// φ(n), how many numbers up to `n` are coprime to it.
+    pub fn phi(&self, n: usize) -> usize {
+        self.phi[n] as usize
+    }
+
+    /// μ(n), which is 0 if `n` has a square factor, and otherwise 1 or -1 as
+    /// `n` has an even or odd number of prime factors.
+    pub fn mu(&self, n: usize) -> i8 {
+        self.mu[n]
+    }
+
+    /// τ(n), the number of divisors.
+    pub fn tau(&self, n: usize) -> usize {
+        self.tau[n] as usize
+    }
+
+    /// σ(n), the sum of the divisors.
+    pub fn sigma(&self, n: usize) -> u64 {
+        self.sigma[n]
+    }
+
+    /// Factorizes `n` by repeatedly dividing out its smallest prime factor.
+    /// Panics if `n` is 0.
+    pub fn factorize(&self, mut n: usize) -> Factorization<usize> {
+        assert!(n > 0, "0 has no factorization");
+        let mut primes = Vec::new();
+        while let Some(p) = self.smallest_prime_factor(n) {
+            primes.push(p);
+            n /= p;
+        }
+        primes.into_iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::number_theory::divisible_by;
+    use crate::number_theory::factorization::factorize;
+
+    fn gcd(a: usize, b: usize) -> usize {
+        if b == 0 {
+            a
+        } else {
+            gcd(b, a % b)
+        }
+    }
+
+    #[test]
+    fn matches_definitions() {
+        let sieve = LinearSieve::new(2_000);
+        assert_eq!(2_000, sieve.limit());
+        for n in 1..=2_000 {
+            let factorization = factorize(n);
+            assert_eq!(factorization, sieve.factorize(n), "{}", n);
+            assert_eq!(
+                factorization.primes().next(),
+                sieve.smallest_prime_factor(n)
+            );
+            assert_eq!(factorization.is_prime(), sieve.is_prime(n), "{}", n);
+            let phi = (1..=n).filter(|&k| gcd(n, k) == 1).count();
+            assert_eq!(phi, sieve.phi(n), "φ({})", n);
+            let mu = match factorization.prime_powers() {
+                powers if powers.iter().any(|&(_, e)| e > 1) => 0,
+                powers if divisible_by(powers.len(), 2) => 1,
+                _ => -1,
+            };
+            assert_eq!(mu, sieve.mu(n), "μ({})", n);
+            assert_eq!(factorization.tau(), sieve.tau(n), "τ({})", n);
+            assert_eq!(factorization.sigma(1) as u64, sieve.sigma(n), "σ({})", n);
+        }
+        assert_eq!(303, sieve.primes().len());
+    }
+
+    #[test]
+    fn small_limits() {
+        let sieve = LinearSieve::new(0);
+        assert_eq!(None, sieve.smallest_prime_factor(0));
+        assert!(sieve.primes().is_empty());
+        let sieve = LinearSieve::new(1);
+        assert_eq!(1, sieve.phi(1));
+        assert_eq!(1, sieve.sigma(1));
+        assert!(!sieve.is_prime(1));
+        assert_eq!(&[2], LinearSieve::new(2).primes());
+    }
+}
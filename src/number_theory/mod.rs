@@ -0,0 +1,29 @@
+//! Number theory helpers shared between the problems.
+
+pub mod factorization;
+pub mod linear_sieve;
+pub mod modular;
+pub mod order;
+pub mod primality;
+pub mod prime_counting;
+pub mod primes;
+pub mod quadratic;
+pub mod roots;
+
+use num_traits::Num;
+
+pub fn divisible_by<T: Num>(number: T, divisor: T) -> bool {
+    (number % divisor).is_zero()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_divisible_by() {
+        assert!(divisible_by(12, 4));
+        assert!(divisible_by(0u64, 7));
+        assert!(!divisible_by(13usize, 4));
+    }
+}
@@ -0,0 +1,382 @@
+//! Modular arithmetic: products and powers that don't overflow, inverses, the
+//! Chinese remainder theorem and a type for residues modulo a constant.
+
+use crate::number_theory::divisible_by;
+use num_traits::{Inv, One, Pow, Zero};
+use std::fmt;
+use std::iter::{Product, Sum};
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/// `a * b % m`, computed in 128 bits so that the product can't overflow.
+pub fn mul_mod(a: u64, b: u64, m: u64) -> u64 {
+    (a as u128 * b as u128 % m as u128) as u64
+}
+
+/// `base^exponent % m` by repeated squaring.
+pub fn pow_mod(base: u64, mut exponent: u64, m: u64) -> u64 {
+    let mut base = base % m;
+    let mut result = 1 % m;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = mul_mod(result, base, m);
+        }
+        base = mul_mod(base, base, m);
+        exponent >>= 1;
+    }
+    result
+}
+
+/// `a * b % m` for `a` and `b` below `m`, without overflowing.
+pub fn mul_mod_u128(a: u128, b: u128, m: u128) -> u128 {
+    if m <= u64::MAX as u128 {
+        return a * b % m;
+    }
+    // Double and add, since the product may not fit
+    let (mut a, mut b, mut result) = (a, b, 0);
+    while b > 0 {
+        if b & 1 == 1 {
+            result = add_mod_u128(result, a, m);
+        }
+        a = add_mod_u128(a, a, m);
+        b >>= 1;
+    }
+    result
+}
+
+/// `(a + b) % m` for `a` and `b` below `m`, without overflowing.
+pub fn add_mod_u128(a: u128, b: u128, m: u128) -> u128 {
+    if a >= m - b {
+        a - (m - b)
+    } else {
+        a + b
+    }
+}
+
+/// `base^exponent % m` by repeated squaring, without overflowing.
+pub fn pow_mod_u128(base: u128, mut exponent: u128, m: u128) -> u128 {
+    let mut base = base % m;
+    let mut result = 1 % m;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = mul_mod_u128(result, base, m);
+        }
+        base = mul_mod_u128(base, base, m);
+        exponent >>= 1;
+    }
+    result
+}
+
+/// The greatest common divisor `g` of `a` and `b`, with Bézout coefficients
+/// `x` and `y` such that `a * x + b * y = g`.
+pub fn extended_gcd(a: u64, b: u64) -> (u64, i128, i128) {
+    let (mut old_r, mut r) = (a as i128, b as i128);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+    (old_r as u64, old_x, old_y)
+}
+
+/// The inverse of `a` modulo `m`, or `None` if they aren't coprime. Panics if
+/// `m` is 0.
+pub fn inverse_mod(a: u64, m: u64) -> Option<u64> {
+    assert!(m > 0, "the modulus must be positive");
+    match extended_gcd(a % m, m) {
+        (1, x, _) => Some(x.rem_euclid(m as i128) as u64),
+        _ => None,
+    }
+}
+
+/// Solves the congruences x ≡ a (mod n) for each `(a, n)`, returning the
+/// solution `x` modulo the least common multiple of the moduli, or `None` if
+/// they contradict each other. The moduli needn't be coprime.
+///
+/// Panics if a modulus is 0 or the least common multiple overflows a `u64`.
+pub fn crt(congruences: &[(u64, u64)]) -> Option<(u64, u64)> {
+    let (mut x, mut modulus) = (0u64, 1u64);
+    for &(a, n) in congruences {
+        assert!(n > 0, "the modulus must be positive");
+        let (g, _, _) = extended_gcd(modulus, n);
+        // x + modulus * k ≡ a (mod n), which is solvable when g divides the
+        // difference, and then k is unique modulo n / g
+        let difference = ((a % n) as u128 + n as u128 - (x % n) as u128) % n as u128;
+        let difference = difference as u64;
+        if !divisible_by(difference, g) {
+            return None;
+        }
+        let n_over_g = n / g;
+        let inverse = inverse_mod(modulus / g, n_over_g).expect("coprime after dividing by g");
+        let k = mul_mod(difference / g, inverse, n_over_g);
+        let lcm = modulus as u128 * n_over_g as u128;
+        let lcm = u64::try_from(lcm).expect("the combined modulus overflows");
+        x = (x as u128 + modulus as u128 * k as u128) as u64;
+        modulus = lcm;
+    }
+    Some((x, modulus))
+}
+
+/// A residue modulo `M`, which must be positive, with arithmetic that wraps
+/// around modulo `M`. For example, the last ten digits of a huge power are
+/// `ModInt::<10_000_000_000>::new(base).pow(exponent)`.
+///
+/// Division multiplies by the inverse, so it panics unless the divisor is
+/// coprime to `M`; when `M` is prime every nonzero residue can be divided by.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
+pub struct ModInt<const M: u64>(u64);
+
+impl<const M: u64> ModInt<M> {
+    pub fn new(value: u64) -> Self {
+        ModInt(value % M)
+    }
+
+    /// The residue as a number in `0..M`.
+    pub fn value(self) -> u64 {
+        self.0
+    }
+
+    pub fn pow(self, exponent: u64) -> Self {
+        ModInt(pow_mod(self.0, exponent, M))
+    }
+
+    /// The inverse, or `None` if the residue isn't coprime to `M`.
+    pub fn inverse(self) -> Option<Self> {
+        inverse_mod(self.0, M).map(ModInt)
+    }
+}
+
+impl<const M: u64> From<u64> for ModInt<M> {
+    fn from(value: u64) -> Self {
+        ModInt::new(value)
+    }
+}
+
+impl<const M: u64> fmt::Display for ModInt<M> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl<const M: u64> Add for ModInt<M> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        ModInt(add_mod_u128(self.0 as u128, other.0 as u128, M as u128) as u64)
+    }
+}
+
+impl<const M: u64> Sub for ModInt<M> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self + -other
+    }
+}
+
+impl<const M: u64> Mul for ModInt<M> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        ModInt(mul_mod(self.0, other.0, M))
+    }
+}
+
+impl<const M: u64> Div for ModInt<M> {
+    type Output = Self;
+
+    fn div(self, other: Self) -> Self {
+        ModInt(mul_mod(self.0, other.inv().0, M))
+    }
+}
+
+impl<const M: u64> Neg for ModInt<M> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        match self.0 {
+            0 => self,
+            value => ModInt(M - value),
+        }
+    }
+}
+
+impl<const M: u64> AddAssign for ModInt<M> {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl<const M: u64> SubAssign for ModInt<M> {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl<const M: u64> MulAssign for ModInt<M> {
+    fn mul_assign(&mut self, other: Self) {
+        *self = *self * other;
+    }
+}
+
+impl<const M: u64> DivAssign for ModInt<M> {
+    fn div_assign(&mut self, other: Self) {
+        *self = *self / other;
+    }
+}
+
+impl<const M: u64> Sum for ModInt<M> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(), Add::add)
+    }
+}
+
+impl<const M: u64> Product for ModInt<M> {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::one(), Mul::mul)
+    }
+}
+
+impl<const M: u64> Zero for ModInt<M> {
+    fn zero() -> Self {
+        ModInt(0)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl<const M: u64> One for ModInt<M> {
+    fn one() -> Self {
+        ModInt(1 % M)
+    }
+}
+
+impl<const M: u64> Pow<u64> for ModInt<M> {
+    type Output = Self;
+
+    fn pow(self, exponent: u64) -> Self {
+        ModInt::pow(self, exponent)
+    }
+}
+
+impl<const M: u64> Inv for ModInt<M> {
+    type Output = Self;
+
+    /// Panics if the residue isn't coprime to `M`.
+    fn inv(self) -> Self {
+        self.inverse()
+            .unwrap_or_else(|| panic!("{} has no inverse modulo {}", self.0, M))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn powers() {
+        assert_eq!(24, pow_mod(2, 10, 1_000));
+        assert_eq!(1, pow_mod(7, 0, 13));
+        assert_eq!(0, pow_mod(7, 0, 1));
+        assert_eq!(0, pow_mod(10, 20, 1_000));
+        // Fermat's little theorem for a prime near 2^64
+        let p = 18_446_744_073_709_551_557;
+        assert_eq!(1, pow_mod(3, p - 1, p));
+        assert_eq!(p - 2, mul_mod(p - 1, 2, p));
+        assert_eq!(1, pow_mod_u128(3, (1 << 89) - 2, (1 << 89) - 1));
+    }
+
+    #[test]
+    fn mul_mod_large() {
+        let m = u128::MAX - 158;
+        assert_eq!(1, mul_mod_u128(m - 1, m - 1, m));
+        assert_eq!(m - 2, mul_mod_u128(m - 1, 2, m));
+        assert_eq!(6, mul_mod_u128(2, 3, m));
+    }
+
+    #[test]
+    fn inverses() {
+        for m in 1..200 {
+            for a in 0..m {
+                let (g, x, y) = extended_gcd(a, m);
+                assert_eq!(g as i128, a as i128 * x + m as i128 * y);
+                match inverse_mod(a, m) {
+                    Some(inverse) => {
+                        assert_eq!(1, g);
+                        assert_eq!(1 % m, mul_mod(a, inverse, m), "{}^-1 mod {}", a, m);
+                    }
+                    None => assert!(g > 1, "{} mod {}", a, m),
+                }
+            }
+        }
+        assert_eq!(Some(500_000_004), inverse_mod(2, 1_000_000_007));
+        assert_eq!(Some(1), inverse_mod(1_000_000_008, 1_000_000_007));
+        assert_eq!(None, inverse_mod(6, 9));
+    }
+
+    #[test]
+    fn chinese_remainders() {
+        assert_eq!(Some((23, 105)), crt(&[(2, 3), (3, 5), (2, 7)]));
+        assert_eq!(Some((0, 1)), crt(&[]));
+        // Moduli that share factors
+        assert_eq!(Some((10, 12)), crt(&[(4, 6), (2, 4)]));
+        assert_eq!(None, crt(&[(1, 6), (2, 4)]));
+        // Residues above their moduli
+        assert_eq!(Some((3, 10)), crt(&[(13, 5), (7, 2)]));
+        for n in 0..420 {
+            let congruences = [(n % 4, 4), (n % 6, 6), (n % 7, 7), (n % 10, 10)];
+            assert_eq!(Some((n, 420)), crt(&congruences));
+        }
+        let (p, q) = (4_294_967_291, 4_294_967_279);
+        assert_eq!(Some((p * q - 1, p * q)), crt(&[(p - 1, p), (q - 1, q)]));
+        // Moduli above 2^63
+        let p = 18_446_744_073_709_551_557;
+        assert_eq!(Some((p - 1, p)), crt(&[(p - 1, p)]));
+        assert_eq!(Some((p - 2, p)), crt(&[(1, 1), (p - 2, p)]));
+        assert_eq!(Some((58, p)), crt(&[(u64::MAX, p)]));
+    }
+
+    #[test]
+    fn mod_int() {
+        type Mod7 = ModInt<7>;
+        let (a, b) = (Mod7::new(12), Mod7::from(4));
+        assert_eq!(5, a.value());
+        assert_eq!(Mod7::new(2), a + b);
+        assert_eq!(Mod7::new(1), a - b);
+        assert_eq!(Mod7::new(6), b - a);
+        assert_eq!(Mod7::new(6), a * b);
+        assert_eq!(a, a / b * b);
+        assert_eq!(Mod7::new(2), -a);
+        assert_eq!(Mod7::new(4), Pow::pow(a, 2));
+        assert_eq!(Some(Mod7::new(2)), b.inverse());
+        assert_eq!(None, Mod7::zero().inverse());
+        assert_eq!("5", a.to_string());
+        let mut c = a;
+        c += b;
+        c *= b;
+        c -= a;
+        c /= b;
+        assert_eq!(Mod7::new(6), c);
+        assert_eq!(Mod7::new(6), (1..=6).map(Mod7::new).product());
+        assert_eq!(Mod7::zero(), (1..=7).map(Mod7::new).sum());
+        assert!(ModInt::<1>::one().is_zero());
+    }
+
+    #[test]
+    fn self_powers() {
+        // The last ten digits of 1^1 + 2^2 + ... + 1000^1000
+        type Mod = ModInt<10_000_000_000>;
+        let sum: Mod = (1..=1000).map(|n| Mod::new(n).pow(n)).sum();
+        assert_eq!(9_110_846_700, sum.value());
+    }
+
+    #[test]
+    #[should_panic(expected = "has no inverse")]
+    fn divide_by_non_unit() {
+        let _ = ModInt::<10>::new(3) / ModInt::new(4);
+    }
+}
@@ -0,0 +1,160 @@
+//! Multiplicative orders, primitive roots and discrete logarithms modulo n.
+
+use crate::number_theory::divisible_by;
+use crate::number_theory::factorization::factorize;
+use crate::number_theory::modular::{extended_gcd, mul_mod, pow_mod};
+use crate::number_theory::roots::isqrt;
+use std::collections::HashMap;
+
+/// The multiplicative order of `a` modulo `n`, the smallest k > 0 with
+/// a^k ≡ 1 (mod n), or `None` if `a` isn't coprime to `n`. Panics if `n` is 0.
+///
+/// The order divides λ(n), so it's found by dividing primes out of λ(n) while
+/// the power stays 1, rather than by trying every k.
+pub fn multiplicative_order(a: u64, n: u64) -> Option<u64> {
+    assert!(n > 0, "the modulus must be positive");
+    if extended_gcd(a % n, n).0 != 1 {
+        return None;
+    }
+    let lambda = factorize(n).carmichael();
+    Some(order_dividing(a, n, lambda))
+}
+
+/// The order of `a` modulo `n`, given a multiple `m` of it.
+fn order_dividing(a: u64, n: u64, m: u64) -> u64 {
+    let mut order = m;
+    for p in factorize(m).primes() {
+        while divisible_by(order, p) && pow_mod(a, order / p, n) == 1 % n {
+            order /= p;
+        }
+    }
+    order
+}
+
+/// The smallest primitive root modulo `n`, a number whose powers run through
+/// every residue coprime to `n`, or `None` if there isn't one. Only 1, 2, 4,
+/// p^k and 2p^k for odd primes p have primitive roots. Panics if `n` is 0.
+pub fn primitive_root(n: u64) -> Option<u64> {
+    assert!(n > 0, "the modulus must be positive");
+    let factorization = factorize(n);
+    let phi = factorization.phi();
+    // The units are cyclic exactly when their exponent is their number
+    if factorization.carmichael() != phi {
+        return None;
+    }
+    let primes = factorize(phi).primes().collect::<Vec<_>>();
+    // g generates the units if its order isn't a proper divisor of φ(n)
+    (0..n).find(|&g| {
+        extended_gcd(g, n).0 == 1 && primes.iter().all(|&p| pow_mod(g, phi / p, n) != 1 % n)
+    })
+}
+
+/// The smallest x ≥ 0 with `base`^x ≡ `target` (mod `n`), or `None` if there
+/// isn't one. Panics if `n` is 0.
+///
+/// Uses baby-step giant-step, which takes O(√n) time and space. `base` needn't
+/// be coprime to `n`: common factors are divided out first.
+pub fn discrete_log(base: u64, target: u64, n: u64) -> Option<u64> {
+    assert!(n > 0, "the modulus must be positive");
+    let (a, mut b, mut n) = (base % n, target % n, n);
+    // Solve a^x ≡ b (mod n) as coefficient * a^(x - offset) ≡ b, dividing
+    // the congruence by g = gcd(a, n) until a is coprime to what's left
+    let mut coefficient = 1 % n;
+    let mut offset = 0;
+    loop {
+        let g = extended_gcd(a, n).0;
+        if g == 1 {
+            break;
+        }
+        if b == coefficient {
+            return Some(offset);
+        }
+        if !divisible_by(b, g) {
+            return None;
+        }
+        b /= g;
+        n /= g;
+        offset += 1;
+        coefficient = (coefficient as u128 * a as u128 / g as u128 % n as u128) as u64;
+    }
+    // Now x - offset = i * steps - j for some 1 ≤ i ≤ steps and 0 ≤ j ≤ steps,
+    // so look for coefficient * a^(i * steps) ≡ b * a^j
+    let steps = isqrt(n) + 1;
+    let mut baby_steps = HashMap::new();
+    let mut value = b;
+    for j in 0..=steps {
+        // Keep the largest j, which gives the smallest x
+        baby_steps.insert(value, j);
+        value = mul_mod(value, a, n);
+    }
+    let giant_step = pow_mod(a, steps, n);
+    let mut value = coefficient;
+    (1..=steps).find_map(|i| {
+        value = mul_mod(value, giant_step, n);
+        baby_steps.get(&value).map(|&j| i * steps - j + offset)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn order_naive(a: u64, n: u64) -> Option<u64> {
+        (1..=n).find(|&k| pow_mod(a, k, n) == 1 % n)
+    }
+
+    #[test]
+    fn orders() {
+        for n in 1..300 {
+            for a in 0..n {
+                assert_eq!(
+                    order_naive(a, n),
+                    multiplicative_order(a, n),
+                    "{} mod {}",
+                    a,
+                    n
+                );
+            }
+        }
+        assert_eq!(Some(6), multiplicative_order(10, 7));
+        assert_eq!(Some(1), multiplicative_order(10, 9));
+        assert_eq!(None, multiplicative_order(10, 12));
+        // 10 is a primitive root modulo this prime
+        assert_eq!(Some(999_982), multiplicative_order(10, 999_983));
+    }
+
+    #[test]
+    fn primitive_roots() {
+        for n in 1..300 {
+            let root = (0..n).find(|&g| order_naive(g, n) == Some(factorize(n).phi()));
+            assert_eq!(root, primitive_root(n), "{}", n);
+        }
+        assert_eq!(Some(5), primitive_root(1_000_000_007));
+        assert_eq!(Some(3), primitive_root(998_244_353));
+        assert_eq!(None, primitive_root(8));
+        assert_eq!(None, primitive_root(15));
+    }
+
+    #[test]
+    fn discrete_logs() {
+        for n in 1..80 {
+            for base in 0..n {
+                for target in 0..n {
+                    let naive = (0..2 * n).find(|&x| pow_mod(base, x, n) == target);
+                    assert_eq!(
+                        naive,
+                        discrete_log(base, target, n),
+                        "{}^x = {} mod {}",
+                        base,
+                        target,
+                        n
+                    );
+                }
+            }
+        }
+        let p = 1_000_000_007;
+        let target = pow_mod(5, 123_456_789, p);
+        assert_eq!(Some(123_456_789), discrete_log(5, target, p));
+        assert_eq!(None, discrete_log(4, 3, 7));
+    }
+}
@@ -0,0 +1,257 @@
+//! Miller–Rabin primality tests for numbers too large to sieve.
+
+use crate::number_theory::divisible_by;
+use crate::number_theory::modular::{mul_mod, mul_mod_u128, pow_mod, pow_mod_u128};
+use num_bigint::BigUint;
+use num_traits::{One, ToPrimitive, Zero};
+
+/// Trial dividing by these first rules out most composites cheaply.
+const SMALL_PRIMES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+/// Testing against these witnesses is enough to decide primality of any
+/// number below 3,215,031,751.
+const SMALL_WITNESSES: [u64; 4] = [2, 3, 5, 7];
+const SMALL_WITNESSES_LIMIT: u64 = 3_215_031_751;
+
+/// Deterministic for all `u64`: no composite below 3.18 * 10^23 is a strong
+/// pseudoprime to all of the first twelve primes.
+pub fn is_prime(n: u64) -> bool {
+    if let Some(prime) = trial_divide(n) {
+        return prime;
+    }
+    let witnesses = if n < SMALL_WITNESSES_LIMIT {
+        &SMALL_WITNESSES[..]
+    } else {
+        &SMALL_PRIMES[..]
+    };
+    let (d, s) = odd_part(n - 1);
+    witnesses
+        .iter()
+        .all(|&a| is_strong_probable_prime(n, a, d, s))
+}
+
+/// Whether `n` is prime, or `None` if trial division by the small primes
+/// doesn't settle it.
+fn trial_divide(n: u64) -> Option<bool> {
+    if n < 2 {
+        return Some(false);
+    }
+    for p in SMALL_PRIMES {
+        if n == p {
+            return Some(true);
+        }
+        if divisible_by(n, p) {
+            return Some(false);
+        }
+    }
+    // Every composite below 41^2 has a factor among the small primes
+    if n < 41 * 41 {
+        return Some(true);
+    }
+    None
+}
+
+/// Splits `n` into `d * 2^s` with `d` odd.
+fn odd_part(n: u64) -> (u64, u32) {
+    let s = n.trailing_zeros();
+    (n >> s, s)
+}
+
+/// The strong probable prime test to base `a` for odd `n = d * 2^s + 1`.
+fn is_strong_probable_prime(n: u64, a: u64, d: u64, s: u32) -> bool {
+    let mut x = pow_mod(a % n, d, n);
+    if x == 1 || x == n - 1 {
+        return true;
+    }
+    for _ in 1..s {
+        x = mul_mod(x, x, n);
+        if x == n - 1 {
+            return true;
+        }
+    }
+    false
+}
+
+/// The first twelve primes decide primality deterministically below this,
+/// which is the smallest composite that is a strong pseudoprime to all of them.
+const DETERMINISTIC_LIMIT: u128 = 318_665_857_834_031_151_167_461;
+
+/// How many pseudo-random witnesses to try beyond the deterministic limit.
+const RANDOM_WITNESSES: usize = 16;
+
+/// Like [`is_prime`], and deterministic below 3.18 * 10^23. Above that, a
+/// composite also has to pass 16 tests to pseudo-random bases, each of which it
+/// passes with probability at most 1/4.
+pub fn is_prime_u128(n: u128) -> bool {
+    if let Ok(n) = u64::try_from(n) {
+        return is_prime(n);
+    }
+    if SMALL_PRIMES.iter().any(|&p| divisible_by(n, p as u128)) {
+        return false;
+    }
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+    let mut random = Xorshift(n as u64 ^ (n >> 64) as u64);
+    let num_random = if n < DETERMINISTIC_LIMIT {
+        0
+    } else {
+        RANDOM_WITNESSES
+    };
+    let random_witnesses = (0..num_random).map(|_| {
+        let a = (random.next() as u128) << 64 | random.next() as u128;
+        // A witness in [2, n - 2]
+        a % (n - 3) + 2
+    });
+    SMALL_PRIMES
+        .iter()
+        .map(|&p| p as u128)
+        .chain(random_witnesses)
+        .all(|a| {
+            let mut x = pow_mod_u128(a, d, n);
+            if x == 1 || x == n - 1 {
+                return true;
+            }
+            for _ in 1..s {
+                x = mul_mod_u128(x, x, n);
+                if x == n - 1 {
+                    return true;
+                }
+            }
+            false
+        })
+}
+
+/// Whether `n` is probably prime, after `rounds` strong probable prime tests.
+///
+/// Numbers that fit in a `u64` are tested deterministically. Otherwise the
+/// first rounds use the small primes as witnesses and the rest use
+/// pseudo-random ones, and a composite passes each round with probability at
+/// most 1/4.
+pub fn is_probable_prime(n: &BigUint, rounds: usize) -> bool {
+    if let Some(n) = n.to_u64() {
+        return is_prime(n);
+    }
+    if SMALL_PRIMES.iter().any(|&p| (n % p).is_zero()) {
+        return false;
+    }
+    let n_minus_one = n - 1u32;
+    let s = n_minus_one.trailing_zeros().expect("n is at least 2^64");
+    let d = &n_minus_one >> s;
+    let mut random = Xorshift(n.iter_u64_digits().fold(SEED, |seed, digit| seed ^ digit));
+    (0..rounds)
+        .map(|round| match SMALL_PRIMES.get(round) {
+            Some(&p) => BigUint::from(p),
+            // A witness in [2, n - 2]
+            None => random.below(&(n - 3u32)) + 2u32,
+        })
+        .all(|a| {
+            let mut x = a.modpow(&d, n);
+            if x.is_one() || x == n_minus_one {
+                return true;
+            }
+            for _ in 1..s {
+                x = &x * &x % n;
+                if x == n_minus_one {
+                    return true;
+                }
+            }
+            false
+        })
+}
+
+const SEED: u64 = 0x9E37_79B9_7F4A_7C15;
+
+/// A xorshift64 generator, which is plenty for picking witnesses.
+struct Xorshift(u64);
+
+impl Xorshift {
+    fn next(&mut self) -> u64 {
+        // The state must never be zero
+        if self.0 == 0 {
+            self.0 = SEED;
+        }
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, bound: &BigUint) -> BigUint {
+        // One more digit than the bound, so that the result is close to uniform
+        let digits = bound.iter_u64_digits().len() + 1;
+        (0..digits).fold(BigUint::zero(), |n, _| (n << 64u32) + self.next()) % bound
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::number_theory::primes::Sieve;
+
+    #[test]
+    fn matches_sieve() {
+        let sieve = Sieve::new(100_000);
+        for n in 0..=100_000 {
+            assert_eq!(sieve.is_prime(n), is_prime(n as u64), "{}", n);
+        }
+    }
+
+    #[test]
+    fn large() {
+        assert!(is_prime(1_000_000_007));
+        assert!(is_prime(4_294_967_291));
+        assert!(is_prime(18_446_744_073_709_551_557));
+        assert!(!is_prime(18_446_744_073_709_551_615));
+        assert!(!is_prime(4_294_967_291 * 4_294_967_279));
+    }
+
+    #[test]
+    fn strong_pseudoprimes() {
+        // Strong pseudoprimes to several of the smallest bases
+        for n in [
+            2_047,
+            1_373_653,
+            25_326_001,
+            3_215_031_751,
+            2_152_302_898_747,
+            3_474_749_660_383,
+            341_550_071_728_321,
+            3_825_123_056_546_413_051,
+        ] {
+            assert!(!is_prime(n), "{}", n);
+        }
+        // Carmichael numbers
+        for n in [561, 1_105, 1_729, 2_465, 2_821, 6_601, 8_911] {
+            assert!(!is_prime(n), "{}", n);
+        }
+    }
+
+    #[test]
+    fn u128() {
+        for n in 0..10_000u128 {
+            assert_eq!(is_prime(n as u64), is_prime_u128(n), "{}", n);
+        }
+        // 2^89 - 1 and 2^127 - 1 are Mersenne primes, 2^67 - 1 is not
+        assert!(is_prime_u128((1 << 89) - 1));
+        assert!(is_prime_u128((1 << 127) - 1));
+        assert!(!is_prime_u128((1 << 67) - 1));
+        assert!(!is_prime_u128(4_294_967_291 * 18_446_744_073_709_551_557));
+        // The smallest strong pseudoprime to the first twelve primes
+        assert!(!is_prime_u128(318_665_857_834_031_151_167_461));
+        assert!(is_prime_u128(
+            340_282_366_920_938_463_463_374_607_431_768_211_297
+        ));
+    }
+
+    #[test]
+    fn big() {
+        let mersenne = |p: u32| (BigUint::one() << p) - 1u32;
+        assert!(is_probable_prime(&mersenne(89), 20));
+        assert!(is_probable_prime(&mersenne(127), 20));
+        assert!(is_probable_prime(&mersenne(521), 20));
+        assert!(!is_probable_prime(&mersenne(67), 20));
+        assert!(!is_probable_prime(&(mersenne(89) * mersenne(107)), 20));
+        assert!(is_probable_prime(&BigUint::from(1_000_000_007u64), 1));
+        assert!(!is_probable_prime(&(mersenne(61) * mersenne(89)), 20));
+    }
+}
@@ -0,0 +1,199 @@
+//! Counting and summing the primes up to n in O(n^(3/4)) time and O(n^(1/2))
+//! space, without listing them, using Lucy_Hedgehog's method, and finding the
+//! nth prime.
+
+use crate::number_theory::primes::{primes_in_range, Sieve};
+use crate::number_theory::roots::isqrt;
+use std::ops::{Mul, Sub};
+
+/// Below this, [`nth_prime`] sieves up to an upper bound on the nth prime
+/// rather than counting.
+const SIEVE_NTH_PRIME_LIMIT: u64 = 1_000_000;
+
+/// π(n), the number of primes up to and including `n`.
+pub fn prime_pi(n: u64) -> u64 {
+    lucy(n, |m| m.saturating_sub(1), |_| 1)
+}
+
+/// The sum of the primes up to and including `n`.
+pub fn prime_sum(n: u64) -> u128 {
+    lucy(
+        n,
+        |m| {
+            let m = m as u128;
+            (m * (m + 1) / 2).saturating_sub(1)
+        },
+        |p| p as u128,
+    )
+}
+
+/// The `n`th prime, counting 2 as the first. Panics if `n` is 0.
+///
+/// For large `n`, counts the primes up to an estimate of the nth prime and
+/// then sieves the short gap between the estimate and the prime itself, so
+/// that the 10^9th prime takes a fraction of a second.
+pub fn nth_prime(n: u64) -> u64 {
+    assert!(n > 0, "primes are counted from 1");
+    if n < SIEVE_NTH_PRIME_LIMIT {
+        let limit = nth_prime_upper_bound(n) as usize;
+        let prime = Sieve::new(limit).primes().nth(n as usize - 1);
+        return prime.expect("the bound is above the nth prime") as u64;
+    }
+    let estimate = nth_prime_estimate(n).min(nth_prime_upper_bound(n));
+    let count = prime_pi(estimate);
+    if count < n {
+        // The nth prime is the (n - count)th after the estimate
+        let prime = primes_in_range(estimate + 1..nth_prime_upper_bound(n) + 1)
+            .nth((n - count - 1) as usize);
+        return prime.expect("the bound is above the nth prime");
+    }
+    // The nth prime is the (count - n)th before the largest prime up to the
+    // estimate. Primes are about ln(estimate) apart, so look back twice that
+    // far per prime, and further if that wasn't enough.
+    let back = (count - n) as usize;
+    let mut window = 2 * (back as u64 + 1) * (estimate as f64).ln().ceil() as u64;
+    loop {
+        let start = estimate.saturating_sub(window);
+        let primes = primes_in_range(start..estimate + 1).collect::<Vec<_>>();
+        if primes.len() > back {
+            return primes[primes.len() - 1 - back];
+        }
+        window *= 2;
+    }
+}
+
+/// An upper bound on the nth prime: Rosser's theorem gives
+/// p_n < n (ln n + ln ln n) for n ≥ 6, and Dusart's tighter
+/// p_n ≤ n (ln n + ln ln n - 1 + (ln ln n - 2) / ln n) holds for n ≥ 688,383.
+pub fn nth_prime_upper_bound(n: u64) -> u64 {
+    if n < 6 {
+        return 13;
+    }
+    let x = n as f64;
+    let (ln, ln_ln) = (x.ln(), x.ln().ln());
+    let bound = if n >= 688_383 {
+        x * (ln + ln_ln - 1.0 + (ln_ln - 2.0) / ln)
+    } else {
+        x * (ln + ln_ln)
+    };
+    bound.ceil() as u64
+}
+
+/// Cipolla's asymptotic expansion of the nth prime, which is within a
+/// fraction of a percent for large `n` but may be on either side of it.
+fn nth_prime_estimate(n: u64) -> u64 {
+    let x = n as f64;
+    let (ln, ln_ln) = (x.ln(), x.ln().ln());
+    let estimate = x
+        * (ln + ln_ln - 1.0 + (ln_ln - 2.0) / ln
+            - (ln_ln * ln_ln - 6.0 * ln_ln + 11.0) / (2.0 * ln * ln));
+    estimate as u64
+}
+
+/// Sums a completely multiplicative `f` over the primes up to `n`, given its
+/// sums over 2..=m (`sum_from_2`) and its values at primes (`at_prime`).
+///
+/// Only the sums up to the values n / i are needed, of which there are about
+/// 2√n. Starting from the sums over all of 2..=m, sieving by each prime p up
+/// to √n removes the numbers whose smallest prime factor is p:
+/// S(m) -= f(p) * (S(m / p) - S(p - 1)) for m ≥ p^2.
+fn lucy<T, S, F>(n: u64, sum_from_2: S, at_prime: F) -> T
+where
+    T: Copy + PartialEq + Sub<Output = T> + Mul<Output = T>,
+    S: Fn(u64) -> T,
+    F: Fn(u64) -> T,
+{
+    let root = isqrt(n);
+    let r = root as usize;
+    // small[i] = S(i) and large[i] = S(n / i), for i up to √n
+    let mut small = (0..=root).map(&sum_from_2).collect::<Vec<_>>();
+    let mut large = (0..=root)
+        .map(|i| sum_from_2(n.checked_div(i).unwrap_or(0)))
+        .collect::<Vec<_>>();
+    for p in 2..=root {
+        if small[p as usize] == small[p as usize - 1] {
+            // p is composite, so it was removed by a smaller prime
+            continue;
+        }
+        let below_p = small[p as usize - 1];
+        let f_p = at_prime(p);
+        let p_squared = p * p;
+        let last_large = (n / p_squared).min(root) as usize;
+        for i in 1..=last_large {
+            let d = i as u64 * p;
+            let s = if d <= root {
+                large[d as usize]
+            } else {
+                small[(n / d) as usize]
+            };
+            large[i] = large[i] - f_p * (s - below_p);
+        }
+        for i in (p_squared as usize..=r).rev() {
+            small[i] = small[i] - f_p * (small[i / p as usize] - below_p);
+        }
+    }
+    if n == 0 {
+        small[0]
+    } else {
+        large[1]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::number_theory::primality::is_prime;
+    use crate::number_theory::primes::Sieve;
+
+    #[test]
+    fn matches_sieve() {
+        let sieve = Sieve::new(10_000);
+        let mut sum = 0;
+        for n in 0..=10_000 {
+            if sieve.is_prime(n) {
+                sum += n as u128;
+            }
+            assert_eq!(sieve.prime_pi(n) as u64, prime_pi(n as u64), "π({})", n);
+            assert_eq!(sum, prime_sum(n as u64), "sum to {}", n);
+        }
+    }
+
+    #[test]
+    fn nth() {
+        let sieve = Sieve::new(100_000);
+        for (i, p) in sieve.primes().enumerate() {
+            let n = i as u64 + 1;
+            assert_eq!(p as u64, nth_prime(n), "{}", n);
+            assert!(p as u64 <= nth_prime_upper_bound(n), "{}", n);
+        }
+        assert_eq!(104_743, nth_prime(10_001));
+        assert_eq!(15_485_863, nth_prime(1_000_000));
+        assert_eq!(15_485_867, nth_prime(1_000_001));
+        assert_eq!(179_424_673, nth_prime(10_000_000));
+        assert_eq!(2_038_074_743, nth_prime(100_000_000));
+        assert_eq!(22_801_763_489, nth_prime(1_000_000_000));
+    }
+
+    #[test]
+    fn nth_around_estimate() {
+        // Exercise both directions from the estimate
+        for n in (1_000_000..1_000_200).step_by(7) {
+            let p = nth_prime(n);
+            assert_eq!(n, prime_pi(p), "{}", n);
+            assert!(is_prime(p), "{}", n);
+        }
+    }
+
+    #[test]
+    fn powers_of_ten() {
+        let pi = [
+            4, 25, 168, 1_229, 9_592, 78_498, 664_579, 5_761_455, 50_847_534,
+        ];
+        for (i, &expected) in pi.iter().enumerate() {
+            assert_eq!(expected, prime_pi(10u64.pow(i as u32 + 1)));
+        }
+        assert_eq!(37_607_912_018, prime_pi(10u64.pow(12)));
+        assert_eq!(142_913_828_922, prime_sum(2_000_000));
+        assert_eq!(2_220_822_432_581_729_238, prime_sum(10u64.pow(10)));
+    }
+}
@@ -0,0 +1,355 @@
+//! Sieves of Eratosthenes over the odd numbers, packed one bit per number:
+//! [`Sieve`] for all the primes up to a limit, and [`primes_in_range`] for the
+//! primes in a window far from zero.
+
+use crate::number_theory::roots::isqrt;
+use std::ops::Range;
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// How many odd numbers each segment of a segmented sieve covers, chosen so
+/// that a segment fits in L1 cache.
+const SEGMENT_ODDS: usize = 1 << 17;
+
+/// The primes up to and including `limit`.
+///
+/// Only odd numbers are stored, one bit each, with bit `i` set when `2i + 1`
+/// is composite (or 1), so sieving up to 10^9 takes about 60MB.
+#[derive(Debug, Clone)]
+pub struct Sieve {
+    limit: usize,
+    composite: Vec<u64>,
+}
+
+impl Sieve {
+    pub fn new(limit: usize) -> Self {
+        let num_odds = limit.div_ceil(2);
+        let mut composite = vec![0u64; num_odds.div_ceil(WORD_BITS).max(1)];
+        // 1 is not prime
+        composite[0] |= 1;
+        for i in (1..).take_while(|&i| (2 * i + 1) * (2 * i + 1) <= limit) {
+            if is_set(&composite, i) {
+                continue;
+            }
+            let p = 2 * i + 1;
+            // Odd multiples of p, starting at p^2, are 2p apart, so p apart in
+            // the odd-only indexing
+            for j in (p * p / 2..num_odds).step_by(p) {
+                composite[j / WORD_BITS] |= 1 << (j % WORD_BITS);
+            }
+        }
+        Sieve { limit, composite }
+    }
+
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    /// Panics if `n` is above the limit of the sieve.
+    pub fn is_prime(&self, n: usize) -> bool {
+        assert!(
+            n <= self.limit,
+            "{} is above the sieve limit {}",
+            n,
+            self.limit
+        );
+        match n {
+            2 => true,
+            _ if n & 1 == 0 => false,
+            _ => !is_set(&self.composite, n / 2),
+        }
+    }
+
+    /// The number of primes up to and including `n`. Panics if `n` is above the
+    /// limit of the sieve.
+    pub fn prime_pi(&self, n: usize) -> usize {
+        assert!(
+            n <= self.limit,
+            "{} is above the sieve limit {}",
+            n,
+            self.limit
+        );
+        if n < 2 {
+            return 0;
+        }
+        // Count the clear bits for the odd numbers up to n, plus one for 2
+        let num_odds = n.div_ceil(2);
+        let (full_words, rest) = (num_odds / WORD_BITS, num_odds % WORD_BITS);
+        let mut count = self.composite[..full_words]
+            .iter()
+            .map(|word| word.count_zeros() as usize)
+            .sum::<usize>();
+        if rest > 0 {
+            let mask = (1 << rest) - 1;
+            count += (!self.composite[full_words] & mask).count_ones() as usize;
+        }
+        count + 1
+    }
+
+    /// The primes up to the limit, in increasing order.
+    pub fn primes(&self) -> Primes<'_> {
+        Primes {
+            sieve: self,
+            word_index: 0,
+            word: !self.composite[0],
+            yielded_two: self.limit < 2,
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a Sieve {
+    type Item = usize;
+    type IntoIter = Primes<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.primes()
+    }
+}
+
+fn is_set(bits: &[u64], i: usize) -> bool {
+    bits[i / WORD_BITS] & (1 << (i % WORD_BITS)) != 0
+}
+
+/// An iterator over the primes in a [`Sieve`], created by [`Sieve::primes`].
+#[derive(Debug, Clone)]
+pub struct Primes<'a> {
+    sieve: &'a Sieve,
+    word_index: usize,
+    /// The primes left in the current word, as set bits.
+    word: u64,
+    yielded_two: bool,
+}
+
+impl<'a> Iterator for Primes<'a> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.yielded_two {
+            self.yielded_two = true;
+            return Some(2);
+        }
+        while self.word == 0 {
+            self.word_index += 1;
+            self.word = !*self.sieve.composite.get(self.word_index)?;
+        }
+        let bit = self.word.trailing_zeros() as usize;
+        self.word &= self.word - 1;
+        let n = 2 * (self.word_index * WORD_BITS + bit) + 1;
+        if n > self.sieve.limit {
+            // Only the padding bits after the limit are left
+            self.word_index = self.sieve.composite.len();
+            self.word = 0;
+            return None;
+        }
+        Some(n)
+    }
+}
+
+/// All the primes up to and including `limit`.
+pub fn primes_up_to(limit: usize) -> Vec<usize> {
+    Sieve::new(limit).primes().collect()
+}
+
+/// The primes in `range`, in increasing order.
+///
+/// The range is sieved a segment at a time, so memory use is bounded by the
+/// primes up to the square root of its end plus one segment, however large or
+/// far from zero the range is. For example, the primes in
+/// `10^12..10^12 + 10^7` need about 80 thousand sieving primes and a 16KB
+/// segment.
+pub fn primes_in_range(range: Range<u64>) -> SegmentedPrimes {
+    let sieving_limit = isqrt(range.end.saturating_sub(1));
+    let sieving_primes = Sieve::new(sieving_limit as usize)
+        .primes()
+        .skip(1)
+        .map(|p| p as u64)
+        .collect();
+    SegmentedPrimes {
+        sieving_primes,
+        pending_two: range.contains(&2),
+        // The first odd number in the range other than 1
+        next_low: range.start.max(3) | 1,
+        end: range.end,
+        segment: vec![0; SEGMENT_ODDS / WORD_BITS],
+        segment_low: 0,
+        segment_odds: 0,
+        position: 0,
+    }
+}
+
+/// An iterator over the primes in a range, created by [`primes_in_range`].
+#[derive(Debug, Clone)]
+pub struct SegmentedPrimes {
+    /// The odd primes up to the square root of the end of the range.
+    sieving_primes: Vec<u64>,
+    pending_two: bool,
+    /// The first odd number of the next segment.
+    next_low: u64,
+    end: u64,
+    /// Bit `i` is set when `segment_low + 2i` is composite.
+    segment: Vec<u64>,
+    segment_low: u64,
+    segment_odds: usize,
+    /// The index of the next odd number of the segment to check.
+    position: usize,
+}
+
+impl SegmentedPrimes {
+    /// Sieves the next segment, returning `false` if the range is exhausted.
+    fn next_segment(&mut self) -> bool {
+        if self.next_low >= self.end {
+            return false;
+        }
+        let low = self.next_low;
+        let odds = ((self.end - low).div_ceil(2) as usize).min(SEGMENT_ODDS);
+        let high = low + 2 * (odds as u64 - 1);
+        self.segment.fill(0);
+        for &p in self.sieving_primes.iter().take_while(|&&p| p * p <= high) {
+            // The first odd multiple of p in the segment, but not p itself
+            let mut multiple = (p * p).max(low.div_ceil(p) * p);
+            if multiple & 1 == 0 {
+                multiple += p;
+            }
+            let first = ((multiple - low) / 2) as usize;
+            for i in (first..odds).step_by(p as usize) {
+                self.segment[i / WORD_BITS] |= 1 << (i % WORD_BITS);
+            }
+        }
+        self.segment_low = low;
+        self.segment_odds = odds;
+        self.position = 0;
+        self.next_low = high.saturating_add(2);
+        true
+    }
+}
+
+impl Iterator for SegmentedPrimes {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pending_two {
+            self.pending_two = false;
+            return Some(2);
+        }
+        loop {
+            while self.position < self.segment_odds {
+                let i = self.position;
+                self.position += 1;
+                if !is_set(&self.segment, i) {
+                    return Some(self.segment_low + 2 * i as u64);
+                }
+            }
+            if !self.next_segment() {
+                return None;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::number_theory::divisible_by;
+
+    fn is_prime_naive(n: usize) -> bool {
+        n >= 2
+            && (2..)
+                .take_while(|d| d * d <= n)
+                .all(|d| !divisible_by(n, d))
+    }
+
+    #[test]
+    fn small() {
+        assert_eq!(vec![2, 3, 5, 7, 11, 13, 17, 19], primes_up_to(20));
+        assert_eq!(vec![2, 3, 5, 7, 11, 13, 17, 19, 23], primes_up_to(23));
+        assert!(primes_up_to(0).is_empty());
+        assert!(primes_up_to(1).is_empty());
+        assert_eq!(vec![2], primes_up_to(2));
+        assert_eq!(vec![2, 3], primes_up_to(3));
+    }
+
+    #[test]
+    fn matches_trial_division() {
+        // Limits around the word boundaries
+        for limit in [126, 127, 128, 129, 130, 255, 256, 257, 1000] {
+            let sieve = Sieve::new(limit);
+            let expected = (0..=limit)
+                .filter(|&n| is_prime_naive(n))
+                .collect::<Vec<_>>();
+            assert_eq!(expected, sieve.primes().collect::<Vec<_>>(), "{}", limit);
+            for n in 0..=limit {
+                assert_eq!(is_prime_naive(n), sieve.is_prime(n), "{}", n);
+                let pi = expected.iter().take_while(|&&p| p <= n).count();
+                assert_eq!(pi, sieve.prime_pi(n), "{}", n);
+            }
+        }
+    }
+
+    #[test]
+    fn prime_pi() {
+        let sieve = Sieve::new(1_000_000);
+        assert_eq!(25, sieve.prime_pi(100));
+        assert_eq!(168, sieve.prime_pi(1_000));
+        assert_eq!(78_498, sieve.prime_pi(1_000_000));
+        assert_eq!(78_498, sieve.primes().count());
+        assert_eq!(Some(999_983), sieve.primes().last());
+    }
+
+    #[test]
+    fn segmented() {
+        let sieve = Sieve::new(3_000);
+        for (start, end) in [
+            (0, 3_000),
+            (0, 2),
+            (0, 3),
+            (2, 3),
+            (1, 10),
+            (17, 18),
+            (20, 23),
+            (90, 97),
+            (1_000, 2_000),
+        ] {
+            let expected = sieve
+                .primes()
+                .filter(|&p| start <= p && p < end)
+                .map(|p| p as u64)
+                .collect::<Vec<_>>();
+            let actual = primes_in_range(start as u64..end as u64).collect::<Vec<_>>();
+            assert_eq!(expected, actual, "{}..{}", start, end);
+        }
+        let (start, end) = (10, 5);
+        assert_eq!(None, primes_in_range(10..10).next());
+        assert_eq!(None, primes_in_range(start..end).next());
+    }
+
+    #[test]
+    fn segmented_across_segments() {
+        let limit = 3 * SEGMENT_ODDS + 100;
+        let expected = Sieve::new(limit)
+            .primes()
+            .filter(|&p| p >= 1_000)
+            .map(|p| p as u64)
+            .collect::<Vec<_>>();
+        let actual = primes_in_range(1_000..limit as u64 + 1).collect::<Vec<_>>();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn segmented_far_from_zero() {
+        let start = 10u64.pow(12);
+        let primes = primes_in_range(start..start + 1_000).collect::<Vec<_>>();
+        assert_eq!(Some(&1_000_000_000_039), primes.first());
+        assert_eq!(Some(&1_000_000_000_997), primes.last());
+        assert_eq!(37, primes.len());
+        // Check against trial division, which is quick enough for a few
+        for &p in primes.iter().take(3) {
+            assert!((2..=isqrt(p)).all(|d| !divisible_by(p, d)), "{}", p);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn above_limit() {
+        Sieve::new(100).is_prime(101);
+    }
+}
@@ -0,0 +1,343 @@
+//! Quadratic residues: Legendre and Jacobi symbols, and square roots modulo
+//! primes, prime powers and composites.
+
+use crate::number_theory::divisible_by;
+use crate::number_theory::factorization::factorize;
+use crate::number_theory::modular::{crt, inverse_mod, mul_mod, pow_mod};
+
+/// Above this many factors of 2 in p - 1, Tonelli–Shanks, which takes O(s^2)
+/// multiplications for p - 1 = q * 2^s, is slower than Cipolla's algorithm.
+const TONELLI_SHANKS_MAX_TWOS: u32 = 10;
+
+/// The Jacobi symbol (a/n) for odd `n`: the product of the Legendre symbols
+/// (a/p) over the prime factors p of `n`, computed by quadratic reciprocity
+/// without factorizing. It's 0 if `a` and `n` share a factor, and -1 means
+/// `a` isn't a square modulo `n`, but 1 doesn't mean it is unless `n` is
+/// prime. Panics if `n` is even.
+pub fn jacobi(a: u64, n: u64) -> i8 {
+    assert!(n & 1 == 1, "the Jacobi symbol needs an odd modulus");
+    let (mut a, mut n) = (a % n, n);
+    let mut result = 1;
+    while a != 0 {
+        // (2/n) is -1 exactly when n ≡ 3 or 5 (mod 8)
+        let twos = a.trailing_zeros();
+        a >>= twos;
+        if twos & 1 == 1 && matches!(n % 8, 3 | 5) {
+            result = -result;
+        }
+        // Reciprocity flips the sign when both are 3 (mod 4)
+        if a % 4 == 3 && n % 4 == 3 {
+            result = -result;
+        }
+        (a, n) = (n % a, a);
+    }
+    if n == 1 {
+        result
+    } else {
+        0
+    }
+}
+
+/// The Legendre symbol (a/p) for an odd prime `p`: 0 if `p` divides `a`, 1 if
+/// `a` is a nonzero square modulo `p` and -1 otherwise.
+pub fn legendre(a: u64, p: u64) -> i8 {
+    jacobi(a, p)
+}
+
+/// A square root of `a` modulo the prime `p`, the smaller of the two, or
+/// `None` if `a` isn't a square.
+pub fn sqrt_mod_prime(a: u64, p: u64) -> Option<u64> {
+    let a = a % p;
+    if p == 2 || a == 0 {
+        return Some(a);
+    }
+    if legendre(a, p) != 1 {
+        return None;
+    }
+    let root = if (p - 1).trailing_zeros() <= TONELLI_SHANKS_MAX_TWOS {
+        tonelli_shanks(a, p)
+    } else {
+        cipolla(a, p)
+    };
+    Some(root.min(p - root))
+}
+
+/// A square root of the nonzero square `a` modulo the odd prime `p`.
+fn tonelli_shanks(a: u64, p: u64) -> u64 {
+    let s = (p - 1).trailing_zeros();
+    let q = (p - 1) >> s;
+    let z = (2..p)
+        .find(|&z| legendre(z, p) == -1)
+        .expect("p is an odd prime");
+    // Invariant: r^2 ≡ a * t, where t's order divides 2^m and c has order
+    // 2^m, so multiplying t by powers of c^2 eventually makes t 1
+    let (mut m, mut c) = (s, pow_mod(z, q, p));
+    let (mut t, mut r) = (pow_mod(a, q, p), pow_mod(a, q.div_ceil(2), p));
+    while t != 1 {
+        // The least i with t^(2^i) = 1
+        let mut i = 0;
+        let mut power = t;
+        while power != 1 {
+            power = mul_mod(power, power, p);
+            i += 1;
+        }
+        let b = pow_mod(c, 1 << (m - i - 1), p);
+        m = i;
+        c = mul_mod(b, b, p);
+        t = mul_mod(t, c, p);
+        r = mul_mod(r, b, p);
+    }
+    r
+}
+
+/// A square root of the nonzero square `a` modulo the odd prime `p`, by
+/// computing (w + √(w^2 - a))^((p + 1) / 2) in the field of size p^2, where
+/// w^2 - a isn't a square.
+fn cipolla(a: u64, p: u64) -> u64 {
+    let (w, non_residue) = (0..p)
+        .map(|w| (w, sub_mod(mul_mod(w, w, p), a, p)))
+        .find(|&(_, n)| legendre(n, p) == -1)
+        .expect("half the values of w work");
+    // (x1 + y1 ω)(x2 + y2 ω) with ω^2 = non_residue
+    let multiply = |(x1, y1): (u64, u64), (x2, y2): (u64, u64)| {
+        let x = (mul_mod(x1, x2, p) as u128 + mul_mod(mul_mod(y1, y2, p), non_residue, p) as u128)
+            % p as u128;
+        let y = (mul_mod(x1, y2, p) as u128 + mul_mod(x2, y1, p) as u128) % p as u128;
+        (x as u64, y as u64)
+    };
+    let (mut base, mut result) = ((w, 1), (1, 0));
+    let mut exponent = p.div_ceil(2);
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = multiply(result, base);
+        }
+        base = multiply(base, base);
+        exponent >>= 1;
+    }
+    result.0
+}
+
+/// All the square roots of `a` modulo `p^k` for a prime `p`, in increasing
+/// order.
+///
+/// Roots of units are lifted from roots modulo `p` by Hensel's lemma, and
+/// when `p` divides `a` the root must be divisible by p too. Multiples of a
+/// high power of `p` have many roots, up to p^(k/2) of them for 0.
+pub fn sqrt_mod_prime_power(a: u64, p: u64, k: u32) -> Vec<u64> {
+    let modulus = p.checked_pow(k).expect("p^k overflows");
+    let a = a % modulus;
+    if a == 0 {
+        // x^2 ≡ 0 exactly when p^⌈k/2⌉ divides x
+        let step = p.pow(k.div_ceil(2));
+        return (0..modulus).step_by(step as usize).collect();
+    }
+    // a = p^v * b with b a unit, and x = p^j * y with v = 2j and
+    // y^2 ≡ b (mod p^e), so each root y mod p^e gives p^j roots x mod p^k
+    let mut v = 0;
+    let mut b = a;
+    while divisible_by(b, p) {
+        b /= p;
+        v += 1;
+    }
+    if v & 1 == 1 {
+        return Vec::new();
+    }
+    let (j, e) = (v / 2, k - v);
+    let p_e = p.pow(e);
+    let p_j = p.pow(j);
+    let mut roots = unit_sqrt_mod_prime_power(b, p, e)
+        .into_iter()
+        .flat_map(|y| (0..p_j).map(move |t| p_j * (y + t * p_e)))
+        .collect::<Vec<_>>();
+    roots.sort_unstable();
+    roots
+}
+
+/// All the square roots of the unit `b` modulo `p^e`, for `e` ≥ 1.
+fn unit_sqrt_mod_prime_power(b: u64, p: u64, e: u32) -> Vec<u64> {
+    let modulus = p.pow(e);
+    if p == 2 {
+        // The odd squares are 1 (mod 8), and then there are four roots,
+        // ±r and ±r + 2^(e-1)
+        return match e {
+            1 => vec![1],
+            2 if b % 4 == 1 => vec![1, 3],
+            2 => Vec::new(),
+            _ if b % 8 != 1 => Vec::new(),
+            _ => {
+                let mut r = 1;
+                for i in 3..e {
+                    if mul_mod(r, r, 1 << (i + 1)) != b % (1 << (i + 1)) {
+                        r += 1 << (i - 1);
+                    }
+                }
+                let half = modulus / 2;
+                let mut roots = vec![
+                    r,
+                    modulus - r,
+                    (r + half) % modulus,
+                    (modulus - r + half) % modulus,
+                ];
+                roots.sort_unstable();
+                roots
+            }
+        };
+    }
+    let mut r = match sqrt_mod_prime(b, p) {
+        Some(r) => r,
+        None => return Vec::new(),
+    };
+    // The Newton step r - (r^2 - b) / 2r turns a root modulo p^i into one
+    // modulo p^(i + 1), since 2r is a unit
+    let mut power = p;
+    for _ in 1..e {
+        power *= p;
+        let error = sub_mod(mul_mod(r, r, power), b % power, power);
+        let inverse = inverse_mod(mul_mod(2, r, power), power).expect("2r is a unit");
+        r = sub_mod(r, mul_mod(error, inverse, power), power);
+    }
+    let mut roots = vec![r, modulus - r];
+    roots.sort_unstable();
+    roots
+}
+
+/// All the square roots of `a` modulo `n`, in increasing order, combining the
+/// roots modulo each prime power in `n` by the Chinese remainder theorem.
+/// Panics if `n` is 0.
+pub fn sqrt_mod(a: u64, n: u64) -> Vec<u64> {
+    assert!(n > 0, "the modulus must be positive");
+    let mut roots = vec![0];
+    let mut modulus = 1;
+    for &(p, k) in factorize(n).prime_powers() {
+        let p_k = p.pow(k);
+        let prime_power_roots = sqrt_mod_prime_power(a, p, k);
+        roots = roots
+            .iter()
+            .flat_map(|&x| {
+                prime_power_roots.iter().map(move |&y| {
+                    let (root, _) = crt(&[(x, modulus), (y, p_k)]).expect("coprime moduli");
+                    root
+                })
+            })
+            .collect();
+        modulus *= p_k;
+    }
+    roots.sort_unstable();
+    roots
+}
+
+/// `(a - b) % m` for `a` and `b` below `m`, without overflowing.
+fn sub_mod(a: u64, b: u64, m: u64) -> u64 {
+    if a >= b {
+        a - b
+    } else {
+        a + (m - b)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::number_theory::primes::Sieve;
+
+    fn sqrt_mod_naive(a: u64, n: u64) -> Vec<u64> {
+        (0..n).filter(|&x| x * x % n == a % n).collect()
+    }
+
+    #[test]
+    fn symbols() {
+        let sieve = Sieve::new(200);
+        for p in sieve.primes().skip(1) {
+            let p = p as u64;
+            for a in 0..2 * p {
+                // Euler's criterion
+                let expected = match pow_mod(a, (p - 1) / 2, p) {
+                    0 => 0,
+                    1 => 1,
+                    _ => -1,
+                };
+                assert_eq!(expected, legendre(a, p), "({}/{})", a, p);
+            }
+        }
+        for n in (1..300).step_by(2) {
+            for a in 0..n {
+                let expected = factorize(n)
+                    .prime_powers()
+                    .iter()
+                    .map(|&(p, k)| legendre(a, p).pow(k))
+                    .product::<i8>();
+                assert_eq!(expected, jacobi(a, n), "({}/{})", a, n);
+            }
+        }
+        assert_eq!(-1, jacobi(1_001, 9_907));
+        assert_eq!(1, jacobi(19, 45));
+    }
+
+    #[test]
+    fn prime_roots() {
+        let sieve = Sieve::new(2_000);
+        for p in sieve.primes() {
+            let p = p as u64;
+            for a in 0..p {
+                let expected = sqrt_mod_naive(a, p).first().copied();
+                assert_eq!(expected, sqrt_mod_prime(a, p), "√{} mod {}", a, p);
+                if p > 2 && expected.is_some_and(|root| root > 0) {
+                    let roots = [tonelli_shanks(a, p), cipolla(a, p)];
+                    for root in roots {
+                        assert_eq!(a, mul_mod(root, root, p), "√{} mod {}", a, p);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn large_primes() {
+        // 998244353 - 1 = 119 * 2^23, so this takes Cipolla's algorithm
+        for p in [998_244_353, 1_000_000_007, 18_446_744_073_709_551_557] {
+            for x in [2, 3, 12_345, 987_654_321, p / 3] {
+                let a = mul_mod(x, x, p);
+                let root = sqrt_mod_prime(a, p).unwrap();
+                assert_eq!(x.min(p - x), root, "√{} mod {}", a, p);
+                assert_eq!(a, mul_mod(tonelli_shanks(a, p), tonelli_shanks(a, p), p));
+                assert_eq!(a, mul_mod(cipolla(a, p), cipolla(a, p), p));
+            }
+        }
+        assert_eq!(None, sqrt_mod_prime(5, 998_244_353));
+    }
+
+    #[test]
+    fn prime_powers() {
+        for (p, max_k) in [(2u64, 10), (3, 6), (5, 4), (7, 3), (11, 2)] {
+            for k in 1..=max_k {
+                let modulus = p.pow(k);
+                for a in 0..modulus {
+                    assert_eq!(
+                        sqrt_mod_naive(a, modulus),
+                        sqrt_mod_prime_power(a, p, k),
+                        "√{} mod {}^{}",
+                        a,
+                        p,
+                        k
+                    );
+                }
+            }
+        }
+        let p_k = 1_000_000_007u64.pow(2);
+        let a = mul_mod(123_456_789_012, 123_456_789_012, p_k);
+        assert!(sqrt_mod_prime_power(a, 1_000_000_007, 2).contains(&123_456_789_012));
+    }
+
+    #[test]
+    fn composites() {
+        for n in 1..400 {
+            for a in 0..n {
+                assert_eq!(sqrt_mod_naive(a, n), sqrt_mod(a, n), "√{} mod {}", a, n);
+            }
+        }
+        assert_eq!(vec![1, 4, 11, 14], sqrt_mod(1, 15));
+        assert!(sqrt_mod(2, 15).is_empty());
+        let p = 18_446_744_073_709_551_557;
+        assert_eq!(vec![2, p - 2], sqrt_mod(4, p));
+    }
+}
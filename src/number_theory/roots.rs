@@ -0,0 +1,208 @@
+//! Exact integer roots, and telling squares and other perfect powers apart,
+//! for `u64`, `u128` and `BigUint`.
+
+use crate::number_theory::primality::is_prime;
+use num_bigint::BigUint;
+
+/// Numbers with exact integer roots.
+pub trait Root: Sized + Clone + PartialEq {
+    /// ⌊n^(1/k)⌋, the largest r with r^k ≤ n. Panics if `k` is 0.
+    fn iroot(&self, k: u32) -> Self;
+
+    /// The number of bits needed to write the number, which is 0 for 0.
+    fn bits(&self) -> u64;
+
+    /// The number to the power `k`.
+    fn power(&self, k: u32) -> Self;
+}
+
+macro_rules! impl_root {
+    ($t:ty) => {
+        impl Root for $t {
+            fn iroot(&self, k: u32) -> Self {
+                assert!(k > 0, "there is no 0th root");
+                let n = *self;
+                if k == 1 || n < 2 {
+                    return n;
+                }
+                let bits = self.bits() as u32;
+                if k >= bits {
+                    return 1;
+                }
+                // Newton's method for x^k - n decreases steadily to the root
+                // from any start above it, such as 2^⌈bits / k⌉
+                let k_minus_one = (k - 1) as $t;
+                let mut x: $t = 1 << bits.div_ceil(k);
+                loop {
+                    let quotient = x.checked_pow(k - 1).map_or(0, |power| n / power);
+                    let next = (k_minus_one * x + quotient) / k as $t;
+                    if next >= x {
+                        return x;
+                    }
+                    x = next;
+                }
+            }
+
+            fn bits(&self) -> u64 {
+                (<$t>::BITS - self.leading_zeros()) as u64
+            }
+
+            fn power(&self, k: u32) -> Self {
+                self.pow(k)
+            }
+        }
+    };
+}
+
+impl_root!(u64);
+impl_root!(u128);
+
+impl Root for BigUint {
+    fn iroot(&self, k: u32) -> Self {
+        self.nth_root(k)
+    }
+
+    fn bits(&self) -> u64 {
+        BigUint::bits(self)
+    }
+
+    fn power(&self, k: u32) -> Self {
+        self.pow(k)
+    }
+}
+
+/// ⌊√n⌋.
+pub fn isqrt<T: Root>(n: T) -> T {
+    n.iroot(2)
+}
+
+/// ⌊∛n⌋.
+pub fn icbrt<T: Root>(n: T) -> T {
+    n.iroot(3)
+}
+
+/// ⌊n^(1/k)⌋. Panics if `k` is 0.
+pub fn iroot<T: Root>(n: T, k: u32) -> T {
+    n.iroot(k)
+}
+
+/// Whether `n` is a perfect square.
+pub fn is_square<T: Root>(n: T) -> bool {
+    exact_root(&n, 2).is_some()
+}
+
+/// The root of `n` if it's a `k`th power.
+fn exact_root<T: Root>(n: &T, k: u32) -> Option<T> {
+    let root = n.iroot(k);
+    if root.power(k) == *n {
+        Some(root)
+    } else {
+        None
+    }
+}
+
+/// Writes `n` as base^exponent with exponent ≥ 2 and as large as possible, or
+/// `None` if `n` isn't a perfect power. 0 and 1 are powers of themselves to
+/// any exponent, so they're `None` too.
+pub fn perfect_power<T: Root>(n: T) -> Option<(T, u32)> {
+    let bits = n.bits();
+    if bits < 2 {
+        return None;
+    }
+    // If n is a power, it's a pth power for a prime p, and its pth root is
+    // smaller than 2^(bits / p), so p < bits
+    let (root, p) = (2..bits as u32)
+        .filter(|&p| is_prime(p as u64))
+        .find_map(|p| exact_root(&n, p).map(|root| (root, p)))?;
+    Some(match perfect_power(root.clone()) {
+        Some((base, exponent)) => (base, exponent * p),
+        None => (root, p),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use num_traits::One;
+
+    #[test]
+    fn small() {
+        for n in 0..10_000u64 {
+            let root = (0..=n).rev().find(|r| r * r <= n).unwrap();
+            assert_eq!(root, isqrt(n), "√{}", n);
+            assert_eq!(root as u128, isqrt(n as u128), "√{}", n);
+            assert_eq!(BigUint::from(root), isqrt(BigUint::from(n)), "√{}", n);
+            assert_eq!(root * root == n, is_square(n), "{}", n);
+            let root = (0..=n).rev().find(|r| r * r * r <= n).unwrap();
+            assert_eq!(root, icbrt(n), "∛{}", n);
+            assert_eq!(root as u128, icbrt(n as u128), "∛{}", n);
+        }
+        assert_eq!(100, iroot(100u64, 1));
+        assert_eq!(3, iroot(1_023u64, 6));
+        assert_eq!(4, iroot(4_096u64, 6));
+    }
+
+    #[test]
+    fn extremes() {
+        assert_eq!(u32::MAX as u64, isqrt(u64::MAX));
+        assert_eq!(u64::MAX as u128, isqrt(u128::MAX));
+        assert_eq!(2_642_245, icbrt(u64::MAX));
+        assert_eq!(6_981_463_658_331, icbrt(u128::MAX));
+        assert_eq!(1, iroot(u64::MAX, 64));
+        assert_eq!(2, iroot(u64::MAX, 63));
+        assert_eq!(3, iroot(u128::MAX, 80));
+        for k in 1..70 {
+            for n in [u64::MAX, u64::MAX - 1, 1 << 62, (1 << 62) - 1] {
+                let root = iroot(n, k) as u128;
+                assert!(root.pow(k) <= n as u128, "{}^(1/{})", n, k);
+                assert!((root + 1)
+                    .checked_pow(k)
+                    .is_none_or(|power| power > n as u128));
+            }
+        }
+        // Squares of large numbers and their neighbours, where floating
+        // point square roots go wrong
+        for r in [94_906_265u64, 4_294_967_295, (1 << 53) + 1] {
+            let square = r as u128 * r as u128;
+            assert_eq!(r as u128, isqrt(square));
+            assert_eq!(r as u128 - 1, isqrt(square - 1));
+            assert!(is_square(square));
+            assert!(!is_square(square + 1));
+        }
+    }
+
+    #[test]
+    fn big() {
+        let n = (BigUint::one() << 1_000u32) + 12_345u32;
+        let root = isqrt(n.clone());
+        assert!(&root * &root <= n);
+        assert!((&root + 1u32).pow(2) > n);
+        assert_eq!(BigUint::one() << 100u32, iroot(n, 10));
+        assert!(is_square(BigUint::from(3u32).pow(300)));
+    }
+
+    #[test]
+    fn perfect_powers() {
+        for n in 0..2_000u64 {
+            let expected = (2..12)
+                .rev()
+                .find_map(|k| {
+                    (2..=n)
+                        .find(|b| b.checked_pow(k) == Some(n))
+                        .map(|b| (b, k))
+                })
+                .filter(|_| n > 1);
+            assert_eq!(expected, perfect_power(n), "{}", n);
+        }
+        assert_eq!(Some((2, 63)), perfect_power(1u64 << 63));
+        assert_eq!(Some((6, 24)), perfect_power(6u128.pow(24)));
+        assert_eq!(Some((10, 18)), perfect_power(10u64.pow(18)));
+        assert_eq!(Some((12, 2)), perfect_power(144u128));
+        assert_eq!(None, perfect_power(u64::MAX));
+        assert_eq!(None, perfect_power(1_000_000_007u64 * 1_000_000_009));
+        assert_eq!(
+            Some((BigUint::from(6u32), 120)),
+            perfect_power(BigUint::from(36u32).pow(60))
+        );
+    }
+}
@@ -0,0 +1,344 @@
+use std::any::Any;
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+const LIST_SEPARATOR: char = ',';
+
+/// A type that can be used as a problem parameter and parsed from the command
+/// line.
+pub trait ParamValue: FromStr + fmt::Display + Clone + Send + Sync + 'static {
+    fn type_name() -> String;
+}
+
+macro_rules! impl_param_value {
+    ($($t:ty),*) => {
+        $(
+            impl ParamValue for $t {
+                fn type_name() -> String {
+                    String::from(stringify!($t))
+                }
+            }
+        )*
+    };
+}
+
+impl_param_value!(u8, u32, u64, u128, usize, i32, i64);
+
+/// A comma-separated list parameter, e.g. `divisors=3,5,7`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct List<T>(pub Vec<T>);
+
+impl<T: FromStr> FromStr for List<T> {
+    type Err = T::Err;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split(LIST_SEPARATOR)
+            .map(|item| item.trim().parse())
+            .collect::<Result<_, _>>()
+            .map(List)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for List<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let items = self.0.iter().map(T::to_string).collect::<Vec<_>>();
+        write!(f, "{}", items.join(&LIST_SEPARATOR.to_string()))
+    }
+}
+
+impl<T: ParamValue> ParamValue for List<T> {
+    fn type_name() -> String {
+        format!("list of {}", T::type_name())
+    }
+}
+
+type BoxedValue = Box<dyn Any + Send + Sync>;
+
+type Check = Box<dyn Fn(&BoxedValue) -> Result<(), String> + Send + Sync>;
+
+/// A parameter a problem accepts, declared along with its default value.
+pub struct Param {
+    name: &'static str,
+    description: &'static str,
+    type_name: String,
+    default: BoxedValue,
+    default_string: String,
+    parse: fn(&str) -> Option<BoxedValue>,
+    checks: Vec<Check>,
+}
+
+impl Param {
+    pub fn new<T: ParamValue>(name: &'static str, description: &'static str, default: T) -> Self {
+        Param {
+            name,
+            description,
+            type_name: T::type_name(),
+            default_string: default.to_string(),
+            default: Box::new(default),
+            parse: parse_boxed::<T>,
+            checks: Vec::new(),
+        }
+    }
+
+    /// Rejects overridden values for which `check` returns an error, so that
+    /// values the solver can't handle are reported before it runs. Panics if
+    /// `T` isn't the type the parameter was declared with.
+    pub fn check<T: ParamValue>(
+        mut self,
+        check: impl Fn(&T) -> Result<(), String> + Send + Sync + 'static,
+    ) -> Self {
+        assert_eq!(
+            T::type_name(),
+            self.type_name,
+            "Parameter {} checked as the wrong type",
+            self.name
+        );
+        self.checks.push(Box::new(move |value| {
+            check(value.downcast_ref().expect("checked the type above"))
+        }));
+        self
+    }
+
+    /// Rejects overridden values below `min`.
+    pub fn at_least<T: ParamValue + PartialOrd>(self, min: T) -> Self {
+        self.check(move |value: &T| {
+            if *value >= min {
+                Ok(())
+            } else {
+                Err(format!("must be at least {}", min))
+            }
+        })
+    }
+
+    /// Rejects overridden values above `max`.
+    pub fn at_most<T: ParamValue + PartialOrd>(self, max: T) -> Self {
+        self.check(move |value: &T| {
+            if *value <= max {
+                Ok(())
+            } else {
+                Err(format!("must be at most {}", max))
+            }
+        })
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn description(&self) -> &'static str {
+        self.description
+    }
+
+    pub fn type_name(&self) -> &str {
+        &self.type_name
+    }
+
+    pub fn default_string(&self) -> &str {
+        &self.default_string
+    }
+}
+
+fn parse_boxed<T: ParamValue>(s: &str) -> Option<BoxedValue> {
+    s.trim()
+        .parse::<T>()
+        .ok()
+        .map(|value| Box::new(value) as BoxedValue)
+}
+
+/// A `name=value` override given on the command line.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Override {
+    pub name: String,
+    pub value: String,
+}
+
+impl FromStr for Override {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, value) = s
+            .split_once('=')
+            .ok_or_else(|| format!("Expected name=value, found \"{}\"", s))?;
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(format!("Missing parameter name in \"{}\"", s));
+        }
+        Ok(Override {
+            name: name.to_string(),
+            value: value.to_string(),
+        })
+    }
+}
+
+/// Parameter values passed to a solver: the declared defaults with any
+/// overrides applied.
+pub struct Params {
+    values: HashMap<&'static str, BoxedValue>,
+}
+
+impl Params {
+    /// Overrides that don't name one of the declared parameters are ignored,
+    /// so that one set of overrides can be applied to several problems.
+    pub fn new(declared: Vec<Param>, overrides: &[Override]) -> Result<Self, String> {
+        let mut values = HashMap::new();
+        for param in declared {
+            let value = match overrides.iter().rev().find(|o| o.name == param.name) {
+                None => param.default,
+                Some(o) => {
+                    let value = (param.parse)(&o.value).ok_or_else(|| {
+                        format!(
+                            "Invalid value \"{}\" for parameter {} (expected {})",
+                            o.value, param.name, param.type_name
+                        )
+                    })?;
+                    for check in &param.checks {
+                        check(&value).map_err(|reason| {
+                            format!(
+                                "Invalid value \"{}\" for parameter {}: {}",
+                                o.value, param.name, reason
+                            )
+                        })?;
+                    }
+                    value
+                }
+            };
+            values.insert(param.name, value);
+        }
+        Ok(Params { values })
+    }
+
+    /// Panics if `name` was not declared with type `T`, which is a bug in the
+    /// problem rather than bad input.
+    pub fn get<T: ParamValue>(&self, name: &str) -> T {
+        self.values
+            .get(name)
+            .and_then(|value| value.downcast_ref::<T>())
+            .unwrap_or_else(|| panic!("No parameter {} of type {}", name, T::type_name()))
+            .clone()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn declared() -> Vec<Param> {
+        vec![
+            Param::new("limit", "Upper bound", 1_000u32),
+            Param::new("divisors", "Divisors", List(vec![3u32, 5])),
+        ]
+    }
+
+    fn overrides(strings: &[&str]) -> Vec<Override> {
+        strings.iter().map(|s| s.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn defaults() {
+        let params = Params::new(declared(), &[]).unwrap();
+        assert_eq!(1_000, params.get::<u32>("limit"));
+        assert_eq!(List(vec![3, 5]), params.get::<List<u32>>("divisors"));
+    }
+
+    #[test]
+    fn overridden() {
+        let params = Params::new(declared(), &overrides(&["limit=10", "divisors=2, 7"])).unwrap();
+        assert_eq!(10, params.get::<u32>("limit"));
+        assert_eq!(List(vec![2, 7]), params.get::<List<u32>>("divisors"));
+    }
+
+    #[test]
+    fn last_override_wins() {
+        let params = Params::new(declared(), &overrides(&["limit=10", "limit=20"])).unwrap();
+        assert_eq!(20, params.get::<u32>("limit"));
+    }
+
+    #[test]
+    fn unknown_overrides_are_ignored() {
+        let params = Params::new(declared(), &overrides(&["width=3"])).unwrap();
+        assert_eq!(1_000, params.get::<u32>("limit"));
+    }
+
+    #[test]
+    fn type_checked() {
+        for bad in ["limit=-1", "limit=abc", "limit=99999999999", "divisors=3,x"] {
+            assert!(
+                Params::new(declared(), &overrides(&[bad])).is_err(),
+                "{}",
+                bad
+            );
+        }
+    }
+
+    #[test]
+    fn checked() {
+        let declared = || {
+            vec![
+                Param::new("limit", "Upper bound", 1_000u32)
+                    .at_least(1u32)
+                    .at_most(5_000u32),
+                Param::new("width", "Width", 5u64).check(|width: &u64| match width % 2 {
+                    1 => Ok(()),
+                    _ => Err(String::from("must be odd")),
+                }),
+            ]
+        };
+        for good in ["limit=1", "limit=5000", "width=7"] {
+            assert!(
+                Params::new(declared(), &overrides(&[good])).is_ok(),
+                "{}",
+                good
+            );
+        }
+        assert_eq!(
+            Err(String::from(
+                "Invalid value \"0\" for parameter limit: must be at least 1"
+            )),
+            Params::new(declared(), &overrides(&["limit=0"])).map(|_| ())
+        );
+        for bad in ["limit=5001", "width=4"] {
+            assert!(
+                Params::new(declared(), &overrides(&[bad])).is_err(),
+                "{}",
+                bad
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn checked_as_wrong_type() {
+        Param::new("limit", "Upper bound", 1_000u32).at_least(1u64);
+    }
+
+    #[test]
+    #[should_panic]
+    fn wrong_type() {
+        let params = Params::new(declared(), &[]).unwrap();
+        params.get::<u64>("limit");
+    }
+
+    #[test]
+    fn parse_override() {
+        assert_eq!(
+            Override {
+                name: String::from("min_divisors"),
+                value: String::from("1000"),
+            },
+            "min_divisors=1000".parse().unwrap()
+        );
+        assert!("min_divisors".parse::<Override>().is_err());
+        assert!("=1000".parse::<Override>().is_err());
+    }
+
+    #[test]
+    fn describe() {
+        let params = declared();
+        assert_eq!("limit", params[0].name());
+        assert_eq!("u32", params[0].type_name());
+        assert_eq!("1000", params[0].default_string());
+        assert_eq!("list of u32", params[1].type_name());
+        assert_eq!("3,5", params[1].default_string());
+    }
+}
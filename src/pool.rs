@@ -0,0 +1,63 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+/// Applies `f` to each item on up to `jobs` threads, returning the results in
+/// the same order as `items`. Threads take the next unstarted item as soon as
+/// they finish one, so a few slow items don't hold up the rest.
+pub fn map_ordered<T, R, F>(items: &[T], jobs: usize, f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let jobs = jobs.clamp(1, items.len().max(1));
+    if jobs == 1 {
+        return items.iter().map(f).collect();
+    }
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new((0..items.len()).map(|_| None).collect::<Vec<_>>());
+    thread::scope(|scope| {
+        for _ in 0..jobs {
+            scope.spawn(|| loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let item = match items.get(i) {
+                    Some(item) => item,
+                    None => break,
+                };
+                let result = f(item);
+                results.lock().unwrap()[i] = Some(result);
+            });
+        }
+    });
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|result| result.expect("item was not processed"))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn keeps_order() {
+        let items = (0..50).collect::<Vec<u64>>();
+        for jobs in [1, 4, 100] {
+            let results = map_ordered(&items, jobs, |&i| {
+                // Finish the earlier items last
+                thread::sleep(Duration::from_micros(50 - i));
+                i * i
+            });
+            assert_eq!(items.iter().map(|i| i * i).collect::<Vec<_>>(), results);
+        }
+    }
+
+    #[test]
+    fn empty() {
+        assert!(map_ordered(&[] as &[u8], 4, |&i| i).is_empty());
+    }
+}
@@ -0,0 +1,125 @@
+use crate::params::{Param, Params};
+use num_bigint::BigUint;
+use num_traits::ToPrimitive;
+use std::fmt;
+
+/// A Project Euler problem along with its solution.
+///
+/// Every `src/euler/eulerN.rs` module defines a unit struct `EulerN`
+/// implementing this trait, which is picked up by the registry in
+/// [`crate::euler`].
+pub trait Problem: Sync {
+    /// The problem number on projecteuler.net.
+    fn number(&self) -> usize;
+
+    /// The problem title on projecteuler.net.
+    fn title(&self) -> &'static str;
+
+    /// Parameters that can be overridden from the command line, along with
+    /// their default values.
+    fn params(&self) -> Vec<Param> {
+        Vec::new()
+    }
+
+    /// Solves the problem as stated.
+    fn solve(&self) -> Answer;
+
+    /// Solves the problem with the given parameters. Problems that declare
+    /// parameters override this; `params` always holds a value for each of
+    /// them.
+    fn solve_with(&self, _params: &Params) -> Answer {
+        self.solve()
+    }
+}
+
+/// The answer to a problem, formatted by the caller rather than printed by the
+/// solver.
+///
+/// Integers that fit in an `i128` are always stored as `Integer`, so that
+/// equal answers compare equal regardless of how they were computed.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Answer {
+    Integer(i128),
+    BigInteger(BigUint),
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Integer(n) => write!(f, "{}", n),
+            Answer::BigInteger(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+macro_rules! impl_from_integer {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    Answer::Integer(n.into())
+                }
+            }
+        )*
+    };
+}
+
+impl_from_integer!(i32, i64, i128, u32, u64);
+
+impl From<usize> for Answer {
+    fn from(n: usize) -> Self {
+        Answer::Integer(n as i128)
+    }
+}
+
+impl From<u128> for Answer {
+    fn from(n: u128) -> Self {
+        Answer::from(BigUint::from(n))
+    }
+}
+
+impl From<BigUint> for Answer {
+    fn from(n: BigUint) -> Self {
+        match n.to_i128() {
+            Some(n) => Answer::Integer(n),
+            None => Answer::BigInteger(n),
+        }
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Text(s.to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn display() {
+        assert_eq!("233168", Answer::from(233_168u64).to_string());
+        assert_eq!("-59231", Answer::from(-59_231i32).to_string());
+        assert_eq!("5537376230", Answer::from("5537376230").to_string());
+        let big = BigUint::from(2u32).pow(200);
+        assert_eq!(big.to_string(), Answer::from(big).to_string());
+    }
+
+    #[test]
+    fn big_integers_are_normalized() {
+        assert_eq!(Answer::Integer(1366), Answer::from(BigUint::from(1366u32)));
+        assert_eq!(Answer::Integer(1366), Answer::from(1366u128));
+        let big = BigUint::from(u128::MAX);
+        assert_eq!(Answer::BigInteger(big.clone()), Answer::from(u128::MAX));
+        assert_eq!(Answer::BigInteger(big.clone()), Answer::from(big));
+    }
+}
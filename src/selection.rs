@@ -0,0 +1,163 @@
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+const ALL: &str = "all";
+const LIST_SEPARATOR: char = ',';
+const RANGE_SEPARATOR: &str = "..";
+const INCLUSIVE_MARKER: char = '=';
+
+/// A set of problems requested on the command line, e.g. `all`, `7`, `3,7,10`
+/// or `1..=32`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Selection {
+    All,
+    Items(Vec<Item>),
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Item {
+    Single(usize),
+    Range(RangeInclusive<usize>),
+}
+
+impl Selection {
+    pub fn contains(&self, problem: usize) -> bool {
+        match self {
+            Selection::All => true,
+            Selection::Items(items) => items.iter().any(|item| item.contains(problem)),
+        }
+    }
+
+    /// Problems that were named individually (as opposed to being part of a
+    /// range or `all`), so that missing implementations can be reported.
+    pub fn explicit(&self) -> impl Iterator<Item = usize> + '_ {
+        let items = match self {
+            Selection::All => &[][..],
+            Selection::Items(items) => &items[..],
+        };
+        items.iter().filter_map(|item| match item {
+            Item::Single(problem) => Some(*problem),
+            Item::Range(_) => None,
+        })
+    }
+
+    pub fn is_single(&self) -> bool {
+        matches!(self, Selection::Items(items) if matches!(items[..], [Item::Single(_)]))
+    }
+}
+
+impl Item {
+    fn contains(&self, problem: usize) -> bool {
+        match self {
+            Item::Single(single) => *single == problem,
+            Item::Range(range) => range.contains(&problem),
+        }
+    }
+}
+
+impl FromStr for Selection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.eq_ignore_ascii_case(ALL) {
+            return Ok(Selection::All);
+        }
+        s.split(LIST_SEPARATOR)
+            .map(str::parse)
+            .collect::<Result<_, _>>()
+            .map(Selection::Items)
+    }
+}
+
+impl FromStr for Item {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (start, end) = match s.split_once(RANGE_SEPARATOR) {
+            None => return parse_problem(s).map(Item::Single),
+            Some(pair) => pair,
+        };
+        let start = parse_problem(start)?;
+        let end = match end.strip_prefix(INCLUSIVE_MARKER) {
+            Some(end) => parse_problem(end)?,
+            None => parse_problem(end)?
+                .checked_sub(1)
+                .ok_or_else(|| format!("Empty range \"{}\"", s))?,
+        };
+        if start > end {
+            return Err(format!("Empty range \"{}\"", s));
+        }
+        Ok(Item::Range(start..=end))
+    }
+}
+
+fn parse_problem(s: &str) -> Result<usize, String> {
+    s.trim()
+        .parse()
+        .map_err(|_| format!("Invalid problem number \"{}\"", s))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn all() {
+        assert_eq!(Ok(Selection::All), "all".parse());
+        assert!(Selection::All.contains(1));
+        assert!(Selection::All.contains(67));
+        assert!(!Selection::All.is_single());
+    }
+
+    #[test]
+    fn single() {
+        let selection: Selection = "7".parse().unwrap();
+        assert_eq!(Selection::Items(vec![Item::Single(7)]), selection);
+        assert!(selection.is_single());
+        assert!(selection.contains(7));
+        assert!(!selection.contains(8));
+        assert_eq!(vec![7], selection.explicit().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn list() {
+        let selection: Selection = "3,7, 10".parse().unwrap();
+        assert!(!selection.is_single());
+        for problem in [3, 7, 10] {
+            assert!(selection.contains(problem));
+        }
+        assert!(!selection.contains(4));
+        assert_eq!(vec![3, 7, 10], selection.explicit().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn ranges() {
+        let inclusive: Selection = "1..=32".parse().unwrap();
+        assert_eq!(Selection::Items(vec![Item::Range(1..=32)]), inclusive);
+        assert!(inclusive.contains(32));
+        assert!(!inclusive.contains(33));
+        assert_eq!(0, inclusive.explicit().count());
+
+        let exclusive: Selection = "1..32".parse().unwrap();
+        assert_eq!(Selection::Items(vec![Item::Range(1..=31)]), exclusive);
+        assert!(!exclusive.is_single());
+    }
+
+    #[test]
+    fn mixed() {
+        let selection: Selection = "1..=3,61".parse().unwrap();
+        assert!(selection.contains(2));
+        assert!(selection.contains(61));
+        assert!(!selection.contains(4));
+        assert_eq!(vec![61], selection.explicit().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn invalid() {
+        for s in ["", "x", "1..", "..3", "5..=3", "3..3", "1,,2", "-1"] {
+            assert!(s.parse::<Selection>().is_err(), "{:?}", s);
+        }
+    }
+}
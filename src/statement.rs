@@ -0,0 +1,161 @@
+//! Re-wrapping of the problem statements embedded in each problem's module.
+//!
+//! Prose is re-wrapped, while anything that looks laid out by hand (grids,
+//! tables, equations, indented lists) is left exactly as written.
+
+pub const WRAP_WIDTH: usize = 80;
+
+/// Hard-wrapped prose fills most of each line, so a paragraph with a short
+/// line anywhere but at the end was probably laid out by hand.
+const MIN_FILLED_WIDTH: usize = 60;
+
+pub fn rewrap(text: &str, width: usize) -> String {
+    paragraphs(text)
+        .iter()
+        .map(|lines| {
+            if is_prose(lines) {
+                wrap(&lines.join(" "), width)
+            } else {
+                lines.join("\n")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Splits on blank lines, and also before list items so that each item is
+/// wrapped separately.
+fn paragraphs(text: &str) -> Vec<Vec<&str>> {
+    let mut paragraphs: Vec<Vec<&str>> = Vec::new();
+    let mut current = Vec::new();
+    for line in text.lines().map(str::trim_end) {
+        if line.is_empty() || (is_list_item(line) && is_prose(&current)) {
+            if !current.is_empty() {
+                paragraphs.push(current);
+            }
+            current = Vec::new();
+        }
+        if !line.is_empty() {
+            current.push(line);
+        }
+    }
+    if !current.is_empty() {
+        paragraphs.push(current);
+    }
+    paragraphs
+}
+
+fn is_list_item(line: &str) -> bool {
+    let marker = line.split_whitespace().next().unwrap_or("");
+    marker == "-" || marker == "*" || is_numbered(marker)
+}
+
+fn is_numbered(marker: &str) -> bool {
+    match marker.strip_suffix('.') {
+        Some(number) => !number.is_empty() && number.chars().all(|c| c.is_ascii_digit()),
+        None => false,
+    }
+}
+
+fn is_prose(lines: &[&str]) -> bool {
+    let (_, init) = match lines.split_last() {
+        Some(split) => split,
+        None => return false,
+    };
+    lines.iter().all(|line| is_prose_line(line))
+        && init
+            .iter()
+            .all(|line| line.chars().count() >= MIN_FILLED_WIDTH)
+}
+
+fn is_prose_line(line: &str) -> bool {
+    if line.starts_with(char::is_whitespace) || line.contains('|') {
+        return false;
+    }
+    let non_space = line.chars().filter(|c| !c.is_whitespace()).count();
+    let letters = line.chars().filter(|c| c.is_alphabetic()).count();
+    letters * 2 >= non_space
+}
+
+fn wrap(text: &str, width: usize) -> String {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        if !line.is_empty() && line.chars().count() + 1 + word.chars().count() > width {
+            lines.push(line);
+            line = String::new();
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(word);
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn wraps_prose() {
+        let text = "The sum of the primes below 10 is 2 + 3 + 5 + 7 = 17. Find the sum of all the \
+                    primes below two million.";
+        let expected = "The sum of the primes below 10 is 2 + 3\n\
+                        + 5 + 7 = 17. Find the sum of all the\n\
+                        primes below two million.";
+        assert_eq!(expected, rewrap(text, 40));
+    }
+
+    #[test]
+    fn joins_hard_wrapped_prose() {
+        let text =
+            "If we list all the natural numbers below 10 that are multiples of 3 or 5, we get\n\
+                    3, 5, 6 and 9. The sum of these multiples is 23.";
+        let expected =
+            "If we list all the natural numbers below 10 that are multiples of 3 or 5, we \
+                        get 3, 5, 6 and 9. The sum of these multiples is 23.";
+        assert_eq!(expected, rewrap(text, 200));
+    }
+
+    #[test]
+    fn keeps_paragraphs() {
+        let text = "First paragraph.\n\n\nSecond paragraph.";
+        assert_eq!(
+            "First paragraph.\n\nSecond paragraph.",
+            rewrap(text, WRAP_WIDTH)
+        );
+    }
+
+    #[test]
+    fn keeps_layout() {
+        let cases = [
+            "73167176531330624919225119674426574742355349194934\n\
+             96983520312774506326239578318016984801869478851843",
+            "n → n/2 (n is even)\nn → 3n + 1 (n is odd)",
+            "     *3*\n   *7*  4\n  2  *4*  6\n8   5  *9*  3",
+            "|Triangle            |P(3,n) = n(n+1)/2   |1, 3, 6, 10, 15, ... |",
+            "  - 1 Jan 1900 was a Monday.\n  - Thirty days has September,",
+            "1^2 + 2^2 + ... + 10^2 = 385",
+        ];
+        for text in cases {
+            assert_eq!(text, rewrap(text, 20));
+        }
+    }
+
+    #[test]
+    fn wraps_list_items_separately() {
+        let text =
+            "1. The set is cyclic, in that the last two digits of each number is the first\n\
+                    two digits of the next number (including the last number with the first).\n\
+                    2. Each polygonal type is represented by a different number in the set.";
+        let expected = "1. The set is cyclic, in that the last two digits of each number is the \
+                        first two digits of the next number (including the last number with the \
+                        first).\n\n\
+                        2. Each polygonal type is represented by a different number in the set.";
+        assert_eq!(expected, rewrap(text, 200));
+    }
+}
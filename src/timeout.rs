@@ -0,0 +1,84 @@
+use std::panic;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+const UNITS: [(&str, f64); 4] = [("ms", 0.001), ("s", 1.0), ("m", 60.0), ("h", 3600.0)];
+
+/// Parses a duration such as `60s`, `500ms`, `2m` or `1.5h`. A bare number is
+/// taken to be seconds.
+pub fn parse_duration(s: &str) -> Result<Duration, String> {
+    let s = s.trim();
+    let (number, seconds_per_unit) = UNITS
+        .iter()
+        .find_map(|&(suffix, seconds)| s.strip_suffix(suffix).map(|number| (number, seconds)))
+        .unwrap_or((s, 1.0));
+    number
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .map(|number| number * seconds_per_unit)
+        .filter(|&seconds| seconds > 0.0)
+        .and_then(|seconds| Duration::try_from_secs_f64(seconds).ok())
+        .ok_or_else(|| format!("Invalid duration \"{}\" (expected e.g. 60s or 500ms)", s))
+}
+
+/// Runs `f` on a new thread, returning `None` if it doesn't finish within
+/// `timeout`. Threads can't be killed, so one that times out is left running
+/// in the background until the process exits.
+pub fn with_timeout<R, F>(timeout: Duration, f: F) -> Option<R>
+where
+    R: Send + 'static,
+    F: FnOnce() -> R + Send + 'static,
+{
+    let (sender, receiver) = mpsc::channel();
+    let handle = thread::spawn(move || {
+        // The receiver is gone if we already gave up waiting
+        let _ = sender.send(f());
+    });
+    match receiver.recv_timeout(timeout) {
+        Ok(result) => Some(result),
+        Err(RecvTimeoutError::Timeout) => None,
+        // The sender was dropped without sending, so `f` panicked
+        Err(RecvTimeoutError::Disconnected) => match handle.join() {
+            Err(payload) => panic::resume_unwind(payload),
+            Ok(()) => unreachable!("finished without sending a result"),
+        },
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn durations() {
+        assert_eq!(Ok(Duration::from_secs(60)), parse_duration("60s"));
+        assert_eq!(Ok(Duration::from_secs(60)), parse_duration("60"));
+        assert_eq!(Ok(Duration::from_millis(500)), parse_duration("500ms"));
+        assert_eq!(Ok(Duration::from_secs(120)), parse_duration("2m"));
+        assert_eq!(Ok(Duration::from_secs(5400)), parse_duration("1.5h"));
+        for bad in ["", "s", "0s", "-1s", "ten", "5d", "infs", "1e30"] {
+            assert!(parse_duration(bad).is_err(), "{:?}", bad);
+        }
+    }
+
+    #[test]
+    fn finishes() {
+        assert_eq!(Some(4), with_timeout(Duration::from_secs(10), || 2 + 2));
+    }
+
+    #[test]
+    fn times_out() {
+        let result = with_timeout(Duration::from_millis(10), || {
+            thread::sleep(Duration::from_secs(10));
+        });
+        assert_eq!(None, result);
+    }
+
+    #[test]
+    #[should_panic(expected = "solver failed")]
+    fn propagates_panics() {
+        with_timeout(Duration::from_secs(10), || panic!("solver failed"));
+    }
+}